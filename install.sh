@@ -2,7 +2,9 @@
 set -e
 
 echo "Building goto..."
-cargo build --release
+# Extra arguments go to cargo (e.g. --no-default-features for a build without semantic search,
+# or --no-default-features --features semantic-http for one without ONNX Runtime)
+cargo build --release "$@"
 
 # Install binary
 INSTALL_DIR="${HOME}/.local/bin"
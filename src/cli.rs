@@ -5,12 +5,20 @@ use std::path::PathBuf;
 #[command(name = "goto")]
 #[command(about = "Quickly navigate to projects with fuzzy + semantic search")]
 #[command(version)]
+#[command(
+    after_help = "Exit codes: 0 jumped, 1 no match, 3 ambiguous (prompt cancelled), 4 no match but close names suggested"
+)]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
 
-    /// Search query (fuzzy + semantic search)
-    #[arg(value_name = "QUERY", trailing_var_arg = true)]
+    /// Search query (fuzzy + semantic search), may include filters like lang:rust under:~/work
+    /// and exclusions like -legacy or !old
+    #[arg(
+        value_name = "QUERY",
+        trailing_var_arg = true,
+        allow_hyphen_values = true
+    )]
     pub query: Vec<String>,
 
     /// Show all matches instead of just the best one
@@ -30,6 +38,59 @@ pub struct Cli {
     pub cd_only: bool,
 }
 
+impl Cli {
+    /// Parse the command line, including global flags typed after the query
+    ///
+    /// The query accepts hyphenated words (`-legacy` exclusions), so clap
+    /// leaves `goto api -c` with `-c` inside the query. Known flags are
+    /// moved out of it here instead of being read as exclusions.
+    pub fn parse_args() -> Self {
+        let mut cli = Self::parse();
+        cli.take_trailing_flags();
+        cli
+    }
+
+    fn take_trailing_flags(&mut self) {
+        let mut query = Vec::new();
+        let mut words = std::mem::take(&mut self.query).into_iter().peekable();
+
+        while let Some(word) = words.next() {
+            match word.as_str() {
+                "--all" => self.all = true,
+                "--cd-only" => self.cd_only = true,
+                "--debug" => self.debug = true,
+                "-n" | "--limit" => {
+                    if let Some(limit) = words.peek().and_then(|n| n.parse().ok()) {
+                        self.limit = limit;
+                        words.next();
+                    }
+                }
+                _ => {
+                    let limit = word
+                        .strip_prefix("--limit=")
+                        .or_else(|| word.strip_prefix("-n"));
+                    if let Some(limit) = limit.and_then(|n| n.parse().ok()) {
+                        self.limit = limit;
+                    } else if is_short_flag_group(&word) {
+                        self.all |= word.contains('a');
+                        self.cd_only |= word.contains('c');
+                    } else {
+                        query.push(word);
+                    }
+                }
+            }
+        }
+
+        self.query = query;
+    }
+}
+
+/// `-a`, `-c`, `-ac` or `-ca`
+fn is_short_flag_group(word: &str) -> bool {
+    word.strip_prefix('-')
+        .is_some_and(|flags| !flags.is_empty() && flags.chars().all(|c| c == 'a' || c == 'c'))
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Show recently accessed projects
@@ -85,6 +146,51 @@ pub enum Commands {
 
     /// Run ranking tests from ~/.config/goto/tests.toml
     Test,
+
+    /// Manage named bookmarks that resolve before any search
+    Alias {
+        #[command(subcommand)]
+        action: AliasAction,
+    },
+
+    /// Show what ranking learned from past selections
+    Learned {
+        /// Forget all recorded selections and learned weights
+        #[arg(long)]
+        reset: bool,
+    },
+
+    /// Manage query synonyms (team jargon, abbreviations, codenames)
+    Synonyms {
+        #[command(subcommand)]
+        action: Option<SynonymAction>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AliasAction {
+    /// Create or update an alias
+    Set {
+        /// Alias name (e.g. fe, infra)
+        name: String,
+        /// Directory path or project name/query
+        target: String,
+    },
+
+    /// Remove an alias
+    #[command(visible_alias = "remove")]
+    Rm {
+        /// Alias name
+        name: String,
+    },
+
+    /// List aliases
+    #[command(visible_alias = "list")]
+    Ls {
+        /// Print alias names only (for shell completion)
+        #[arg(long)]
+        names: bool,
+    },
 }
 
 #[derive(Clone, Debug, Default)]
@@ -103,7 +209,73 @@ impl std::str::FromStr for SortOrder {
             "recent" | "r" => Ok(SortOrder::Recent),
             "frecency" | "f" => Ok(SortOrder::Frecency),
             "name" | "n" => Ok(SortOrder::Name),
-            _ => Err(format!("Unknown sort order: {s}. Use: recent, frecency, or name")),
+            _ => Err(format!(
+                "Unknown sort order: {s}. Use: recent, frecency, or name"
+            )),
         }
     }
 }
+
+#[derive(Subcommand)]
+pub enum SynonymAction {
+    /// Expand a term into one or more phrases (e.g. goto synonyms add cp "control plane")
+    Add {
+        /// Term as typed in queries (jargon, abbreviation, codename)
+        term: String,
+        /// Expansions searched along with the term
+        #[arg(required = true)]
+        expansions: Vec<String>,
+    },
+
+    /// Remove a term, or only one of its expansions
+    #[command(visible_alias = "remove")]
+    Rm {
+        /// Term to remove
+        term: String,
+        /// Only remove this expansion
+        expansion: Option<String>,
+    },
+
+    /// List synonyms
+    #[command(visible_alias = "list")]
+    Ls,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Cli {
+        let mut cli = Cli::parse_from(std::iter::once("goto").chain(args.iter().copied()));
+        cli.take_trailing_flags();
+        cli
+    }
+
+    #[test]
+    fn flags_after_the_query_are_flags() {
+        let cli = parse(&["api", "-c", "-a", "--limit", "30"]);
+        assert_eq!(cli.query, ["api"]);
+        assert!(cli.cd_only && cli.all);
+        assert_eq!(cli.limit, 30);
+
+        let cli = parse(&["kafka", "-legacy", "-ac", "-n5", "--debug"]);
+        assert_eq!(cli.query, ["kafka", "-legacy"]);
+        assert!(cli.cd_only && cli.all && cli.debug);
+        assert_eq!(cli.limit, 5);
+    }
+
+    #[test]
+    fn flags_before_the_query_still_work() {
+        let cli = parse(&["-a", "-n", "3", "web", "!old"]);
+        assert_eq!(cli.query, ["web", "!old"]);
+        assert!(cli.all && !cli.cd_only);
+        assert_eq!(cli.limit, 3);
+    }
+
+    #[test]
+    fn limit_needs_a_number() {
+        let cli = parse(&["api", "-n", "-legacy"]);
+        assert_eq!(cli.query, ["api", "-legacy"]);
+        assert_eq!(cli.limit, 10);
+    }
+}
@@ -0,0 +1,252 @@
+use crate::db::Project;
+use crate::ranking::{ScoredProject, SEMANTIC_MIN_THRESHOLD};
+use crate::{fuzzy, lexical};
+
+/// Final-score lead over the runner-up that is enough on its own to jump
+const SCORE_MARGIN: f32 = 6.0;
+
+/// Lead in name edit similarity (0-1) that is enough on its own to jump
+const NAME_SIMILARITY_MARGIN: f32 = 0.3;
+
+/// Lead in frecency, as a share of the frecency weight, that is enough on its own to jump
+const FRECENCY_MARGIN_SHARE: f32 = 0.5;
+
+/// Minimum edit similarity for a project name to be suggested
+const SUGGESTION_MIN_SIMILARITY: f32 = 0.5;
+
+/// Weight of a similarity measured against part of a name (a word or a
+/// prefix) rather than the whole of it
+const PARTIAL_NAME_WEIGHT: f32 = 0.9;
+
+/// Minimum relevance for a rejected candidate to be suggested
+const SUGGESTION_MIN_RELEVANCE: f32 = 35.0;
+
+/// Maximum number of candidates listed when asking or suggesting
+pub const MAX_CHOICES: usize = 3;
+
+/// What to do with the ranked candidates of a query
+#[derive(Debug)]
+pub enum Decision {
+    /// One candidate is clearly the best
+    Jump(ScoredProject),
+    /// Several acceptable candidates are too close to call
+    Ask(Vec<ScoredProject>),
+    /// Nothing is acceptable, but these names are close ("did you mean")
+    Suggest(Vec<Project>),
+    /// Nothing comes close
+    NoMatch,
+}
+
+/// Decide between jumping, asking and suggesting
+///
+/// `ranked` holds every candidate, best first. Between two acceptable
+/// candidates, the lead of the top one is measured three ways (final score,
+/// edit similarity of the name to the query, frecency); each lead is
+/// divided by the margin that would be convincing on its own, and the
+/// jump happens when the sum reaches 1.
+pub fn decide(
+    query: &str,
+    ranked: Vec<ScoredProject>,
+    projects: &[Project],
+    frecency_weight: f32,
+) -> Decision {
+    let acceptable: Vec<&ScoredProject> = ranked
+        .iter()
+        .filter(|c| c.relevance as f64 >= SEMANTIC_MIN_THRESHOLD)
+        .collect();
+
+    match acceptable.as_slice() {
+        [] => suggest(query, &ranked, projects),
+        [top] => Decision::Jump((*top).clone()),
+        [top, second, ..] => {
+            if lead(query, top, second, frecency_weight) >= 1.0 {
+                Decision::Jump((*top).clone())
+            } else {
+                Decision::Ask(acceptable.into_iter().take(MAX_CHOICES).cloned().collect())
+            }
+        }
+    }
+}
+
+/// How convincingly `top` beats `second` (1.0 = enough to jump)
+fn lead(query: &str, top: &ScoredProject, second: &ScoredProject, frecency_weight: f32) -> f32 {
+    let score_lead = (top.score - second.score) / SCORE_MARGIN;
+
+    let name_lead = (fuzzy::edit_similarity(query, &top.project.name)
+        - fuzzy::edit_similarity(query, &second.project.name))
+        / NAME_SIMILARITY_MARGIN;
+
+    let frecency_lead = if frecency_weight > 0.0 {
+        (top.frecency - second.frecency) / (frecency_weight * FRECENCY_MARGIN_SHARE)
+    } else {
+        0.0
+    };
+
+    score_lead + name_lead + frecency_lead
+}
+
+/// Edit similarity of a query to a project name, its words and its prefix
+///
+/// A typo of one word (`kafak`) is far from the whole name
+/// (`kafka-connect`) but close to the word it misspells.
+fn name_similarity(query: &str, name: &str) -> f32 {
+    let query_len = fuzzy::fold(query).chars().count();
+    let prefix: String = fuzzy::fold(name).chars().take(query_len).collect();
+
+    let partial = lexical::tokenize(name)
+        .iter()
+        .chain(std::iter::once(&prefix))
+        .map(|part| fuzzy::edit_similarity(query, part))
+        .fold(0.0_f32, f32::max);
+
+    fuzzy::edit_similarity(query, name).max(partial * PARTIAL_NAME_WEIGHT)
+}
+
+/// Closest project names by edit distance, then the best rejected candidates
+fn suggest(query: &str, ranked: &[ScoredProject], projects: &[Project]) -> Decision {
+    let mut by_name: Vec<(&Project, f32)> = projects
+        .iter()
+        .map(|p| (p, name_similarity(query, &p.name)))
+        .filter(|(_, similarity)| *similarity >= SUGGESTION_MIN_SIMILARITY)
+        .collect();
+    by_name.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(
+                b.0.frecency_score()
+                    .partial_cmp(&a.0.frecency_score())
+                    .unwrap_or(std::cmp::Ordering::Equal),
+            )
+    });
+
+    let mut suggestions: Vec<Project> = Vec::new();
+    let near_misses = ranked
+        .iter()
+        .filter(|c| c.relevance >= SUGGESTION_MIN_RELEVANCE)
+        .map(|c| &c.project);
+    for project in by_name.into_iter().map(|(p, _)| p).chain(near_misses) {
+        if suggestions.len() >= MAX_CHOICES {
+            break;
+        }
+        if !suggestions.iter().any(|s| s.path == project.path) {
+            suggestions.push(project.clone());
+        }
+    }
+
+    if suggestions.is_empty() {
+        Decision::NoMatch
+    } else {
+        Decision::Suggest(suggestions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ranking::NameBoost;
+
+    fn scored(path: &str, relevance: f32, frecency: f32) -> ScoredProject {
+        ScoredProject {
+            project: Project::for_test(path),
+            semantic: None,
+            fuzzy: relevance,
+            boost: NameBoost::None,
+            learned: 0.0,
+            relevance,
+            frecency,
+            proximity: 0.0,
+            score: relevance + frecency,
+        }
+    }
+
+    fn jumped_to(decision: Decision) -> Option<String> {
+        match decision {
+            Decision::Jump(c) => Some(c.project.name),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn single_acceptable_candidate_jumps() {
+        let ranked = vec![
+            scored("/c/billing-api", 80.0, 0.0),
+            scored("/c/web", 40.0, 0.0),
+        ];
+        assert_eq!(
+            jumped_to(decide("billing", ranked, &[], 10.0)).as_deref(),
+            Some("billing-api")
+        );
+    }
+
+    #[test]
+    fn clear_score_lead_jumps() {
+        let ranked = vec![
+            scored("/c/alpha", 80.0, 0.0),
+            scored("/c/beta", 80.0 - SCORE_MARGIN, 0.0),
+        ];
+        assert_eq!(
+            jumped_to(decide("zzz", ranked, &[], 10.0)).as_deref(),
+            Some("alpha")
+        );
+    }
+
+    #[test]
+    fn close_candidates_ask() {
+        let ranked = vec![
+            scored("/c/alpha", 80.0, 0.0),
+            scored("/c/beta", 78.0, 0.0),
+            scored("/c/gamma", 77.0, 0.0),
+            scored("/c/delta", 76.0, 0.0),
+        ];
+        match decide("zzz", ranked, &[], 10.0) {
+            Decision::Ask(candidates) => assert_eq!(candidates.len(), MAX_CHOICES),
+            other => panic!("expected Ask, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn leads_add_up() {
+        // Neither the score lead nor the frecency lead is enough alone, together they are
+        let ranked = vec![scored("/c/alpha", 80.0, 3.0), scored("/c/beta", 77.0, 0.0)];
+        assert_eq!(
+            jumped_to(decide("zzz", ranked, &[], 10.0)).as_deref(),
+            Some("alpha")
+        );
+        let ranked = vec![scored("/c/alpha", 80.0, 0.0), scored("/c/beta", 77.0, 0.0)];
+        assert!(matches!(decide("zzz", ranked, &[], 10.0), Decision::Ask(_)));
+    }
+
+    #[test]
+    fn name_closest_to_the_query_breaks_ties() {
+        let ranked = vec![
+            scored("/c/api", 80.0, 0.0),
+            scored("/c/api-gateway", 80.0, 0.0),
+        ];
+        assert_eq!(
+            jumped_to(decide("api", ranked, &[], 10.0)).as_deref(),
+            Some("api")
+        );
+    }
+
+    #[test]
+    fn typo_of_one_word_is_suggested() {
+        let projects = [
+            Project::for_test("/c/kafka-connect"),
+            Project::for_test("/c/billing-api"),
+        ];
+        match decide("kafak", vec![], &projects, 10.0) {
+            Decision::Suggest(names) => assert_eq!(names[0].name, "kafka-connect"),
+            other => panic!("expected Suggest, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn nothing_close_is_no_match() {
+        let projects = [Project::for_test("/c/kafka-connect")];
+        let ranked = vec![scored("/c/kafka-connect", 20.0, 0.0)];
+        assert!(matches!(
+            decide("zzzzzz", ranked, &projects, 10.0),
+            Decision::NoMatch
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -28,16 +29,124 @@ pub struct Config {
     /// Path patterns to exclude (e.g., node_modules, vendor)
     #[serde(default = "default_exclude_patterns")]
     pub exclude_patterns: Vec<String>,
+
+    /// Also skip directories ignored by .gitignore, .ignore and .gotoignore
+    /// files when scanning for projects
+    #[serde(default)]
+    pub scan_respect_ignore: bool,
+
+    /// Maximum points frecency (how often and how recently you jump
+    /// to a project) adds to a search score (0 disables)
+    #[serde(default = "default_frecency_weight")]
+    pub frecency_weight: f32,
+
+    /// Maximum points added to projects near the current directory
+    /// (siblings of the project you're in, same scan root) (0 disables)
+    #[serde(default = "default_proximity_weight")]
+    pub proximity_weight: f32,
+
+    /// Ask on the terminal which project to open when the top matches are
+    /// too close to call (otherwise the top match is opened)
+    #[serde(default = "default_true")]
+    pub interactive: bool,
+
+    /// Where embeddings are computed: "fastembed" (in-process model)
+    /// or "http" (a local embedding server, see embedding_server)
+    #[serde(default)]
+    pub embedding_backend: EmbeddingBackendKind,
+
+    /// Model used for semantic search: a fastembed model (e.g. "multilingual-e5-small",
+    /// "BAAI/bge-small-en-v1.5") or the server's model name with the http backend.
+    /// Changing it re-embeds every project.
+    #[serde(default = "default_embedding_model")]
+    pub embedding_model: String,
+
+    /// Local embedding server, used when embedding_backend = "http"
+    #[serde(default)]
+    pub embedding_server: Option<EmbeddingServer>,
+
+    /// Team jargon expanded in queries before searching
+    /// (e.g. cp = ["control plane"], phoenix = ["billing-v2"])
+    #[serde(default)]
+    pub synonyms: BTreeMap<String, Vec<String>>,
+}
+
+/// Where embeddings are computed (by default the in-process model, or the
+/// server in builds without one)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbeddingBackendKind {
+    /// In-process ONNX model, downloaded on first use
+    #[cfg_attr(
+        any(feature = "semantic-local", not(feature = "semantic-http")),
+        default
+    )]
+    Fastembed,
+    /// Local HTTP embedding server
+    #[cfg_attr(
+        all(feature = "semantic-http", not(feature = "semantic-local")),
+        default
+    )]
+    Http,
+}
+
+/// A local embedding server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingServer {
+    /// Endpoint: Ollama (http://localhost:11434/api/embed or /api/embeddings)
+    /// or OpenAI-compatible (http://localhost:8080/v1/embeddings)
+    pub url: String,
+
+    /// Dimension of the vectors the model returns (e.g. 768 for nomic-embed-text)
+    pub dim: usize,
+}
+
+/// Model served by DEFAULT_EMBEDDING_SERVER_URL when no server is configured
+#[cfg(feature = "semantic-http")]
+pub const DEFAULT_SERVER_MODEL: &str = "nomic-embed-text";
+
+/// Ollama's batch endpoint on its default port
+#[cfg(feature = "semantic-http")]
+const DEFAULT_EMBEDDING_SERVER_URL: &str = "http://localhost:11434/api/embed";
+
+#[cfg(feature = "semantic-http")]
+impl Default for EmbeddingServer {
+    fn default() -> Self {
+        Self {
+            url: DEFAULT_EMBEDDING_SERVER_URL.to_string(),
+            dim: 768,
+        }
+    }
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// Embedding model used when the config doesn't pick one
+#[cfg(any(feature = "semantic-local", not(feature = "semantic-http")))]
+pub const DEFAULT_EMBEDDING_MODEL: &str = "multilingual-e5-small";
+/// Embedding model used when the config doesn't pick one (served by Ollama
+/// in builds without in-process models)
+#[cfg(all(feature = "semantic-http", not(feature = "semantic-local")))]
+pub const DEFAULT_EMBEDDING_MODEL: &str = DEFAULT_SERVER_MODEL;
+
+fn default_embedding_model() -> String {
+    DEFAULT_EMBEDDING_MODEL.to_string()
+}
+
 fn default_max_depth() -> usize {
     5
 }
 
+fn default_frecency_weight() -> f32 {
+    10.0
+}
+
+fn default_proximity_weight() -> f32 {
+    8.0
+}
+
 fn default_spotlight_paths() -> Vec<PathBuf> {
     if let Some(home) = dirs::home_dir() {
         vec![home]
@@ -76,6 +185,14 @@ impl Default for Config {
             max_depth: 5,
             post_command: Some("claude".to_string()),
             exclude_patterns: default_exclude_patterns(),
+            scan_respect_ignore: false,
+            frecency_weight: default_frecency_weight(),
+            proximity_weight: default_proximity_weight(),
+            interactive: true,
+            embedding_backend: EmbeddingBackendKind::default(),
+            embedding_model: default_embedding_model(),
+            embedding_server: None,
+            synonyms: BTreeMap::new(),
         }
     }
 }
@@ -102,6 +219,7 @@ impl Config {
     }
 
     /// Get the cache directory for embedding models
+    #[cfg(feature = "semantic-local")]
     pub fn model_cache_dir() -> Result<PathBuf> {
         ProjectDirs::from("dev", "goto", "goto")
             .map(|dirs| dirs.cache_dir().to_path_buf())
@@ -129,8 +247,9 @@ impl Config {
         let config_path = Self::config_path()?;
 
         if let Some(parent) = config_path.parent() {
-            std::fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create config directory: {}", parent.display())
+            })?;
         }
 
         let content = toml::to_string_pretty(self)?;
@@ -142,7 +261,8 @@ impl Config {
 
     /// Add a path to scan_paths
     pub fn add_path(&mut self, path: PathBuf) -> Result<()> {
-        let canonical = path.canonicalize()
+        let canonical = path
+            .canonicalize()
             .with_context(|| format!("Path does not exist: {}", path.display()))?;
 
         if !self.scan_paths.contains(&canonical) {
@@ -165,4 +285,39 @@ impl Config {
             Ok(false)
         }
     }
+
+    /// Add expansions for a query term (merged with existing ones)
+    pub fn add_synonyms(&mut self, term: &str, expansions: &[String]) -> Result<()> {
+        let key = term.to_lowercase();
+        let entry = self.synonyms.entry(key).or_default();
+        for expansion in expansions {
+            if !entry.contains(expansion) {
+                entry.push(expansion.clone());
+            }
+        }
+        self.save()
+    }
+
+    /// Remove a term, or only one of its expansions
+    pub fn remove_synonym(&mut self, term: &str, expansion: Option<&str>) -> Result<bool> {
+        let key = term.to_lowercase();
+        let removed = match (expansion, self.synonyms.get_mut(&key)) {
+            (None, _) => self.synonyms.remove(&key).is_some(),
+            (Some(expansion), Some(entry)) => {
+                let before = entry.len();
+                entry.retain(|e| e != expansion);
+                let changed = entry.len() != before;
+                if entry.is_empty() {
+                    self.synonyms.remove(&key);
+                }
+                changed
+            }
+            (Some(_), None) => false,
+        };
+
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
 }
@@ -1,12 +1,29 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use rusqlite::{ffi::sqlite3_auto_extension, params, Connection, OptionalExtension, Transaction};
+#[cfg(feature = "semantic")]
+use rusqlite::ffi::sqlite3_auto_extension;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+#[cfg(feature = "semantic")]
 use sqlite_vec::sqlite3_vec_init;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "semantic")]
 use zerocopy::AsBytes;
 
 use crate::config::Config;
-use crate::embedding::EMBEDDING_DIM;
+#[cfg(feature = "semantic")]
+use crate::embedding;
+use crate::lexical;
+#[cfg(feature = "semantic")]
+use crate::semantic;
+use crate::symbols::{Symbol, SymbolKind};
+
+/// Table whose rows mark a project as indexed: its vectors, or just its
+/// metadata in builds without semantic search
+#[cfg(feature = "semantic")]
+const INDEXED_TABLE: &str = "project_vector_fields";
+#[cfg(not(feature = "semantic"))]
+const INDEXED_TABLE: &str = "project_metadata";
 
 #[derive(Debug, Clone)]
 pub struct Project {
@@ -14,8 +31,9 @@ pub struct Project {
     pub name: String,
     pub last_accessed: DateTime<Utc>,
     pub access_count: i64,
-    #[allow(dead_code)]
     pub source: ProjectSource,
+    /// Workspace root this project is a member of (Cargo/npm/Go/Gradle workspaces)
+    pub parent: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -40,8 +58,44 @@ impl Project {
 
         recency_factor * frequency_factor * 100.0
     }
+
+    /// Scanned project at `path`, never visited, for use in tests
+    #[cfg(test)]
+    pub fn for_test(path: &str) -> Self {
+        let path = PathBuf::from(path);
+        Project {
+            name: path.file_name().unwrap().to_string_lossy().to_string(),
+            path,
+            last_accessed: Utc::now(),
+            access_count: 0,
+            source: ProjectSource::Scan,
+            parent: None,
+        }
+    }
+
+    /// Name shown to the user: `repo/member` for workspace members
+    pub fn display_name(&self) -> String {
+        match self.parent.as_deref().and_then(|p| p.file_name()) {
+            Some(parent) => format!("{}/{}", parent.to_string_lossy(), self.name),
+            None => self.name.clone(),
+        }
+    }
 }
 
+/// Fields stored in the full-text (FTS5) index for a project
+#[derive(Debug, Default)]
+pub struct LexicalFields<'a> {
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+    pub keywords: &'a [String],
+    pub readme: Option<&'a str>,
+    pub types: &'a [String],
+    pub tech: &'a [String],
+}
+
+/// (project id, path, name, stored fingerprint) of an indexed project
+pub type FingerprintRow = (i64, PathBuf, String, Option<String>);
+
 impl std::fmt::Display for ProjectSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -67,26 +121,42 @@ impl std::str::FromStr for ProjectSource {
 
 pub struct Database {
     conn: Connection,
+    /// Dimension of the vectors in project_vectors
+    #[cfg(feature = "semantic")]
+    dim: usize,
 }
 
 impl Database {
     pub fn open() -> Result<Self> {
         // Initialize sqlite-vec extension (must be done before opening connection)
+        #[cfg(feature = "semantic")]
         unsafe {
-            sqlite3_auto_extension(Some(std::mem::transmute(sqlite3_vec_init as *const ())));
+            sqlite3_auto_extension(Some(std::mem::transmute::<
+                *const (),
+                unsafe extern "C" fn(
+                    *mut rusqlite::ffi::sqlite3,
+                    *mut *mut std::os::raw::c_char,
+                    *const rusqlite::ffi::sqlite3_api_routines,
+                ) -> std::os::raw::c_int,
+            >(sqlite3_vec_init as *const ())));
         }
 
         let db_path = Config::db_path()?;
 
         if let Some(parent) = db_path.parent() {
-            std::fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create data directory: {}", parent.display()))?;
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create data directory: {}", parent.display())
+            })?;
         }
 
         let conn = Connection::open(&db_path)
             .with_context(|| format!("Failed to open database: {}", db_path.display()))?;
 
-        let db = Self { conn };
+        let db = Self {
+            conn,
+            #[cfg(feature = "semantic")]
+            dim: embedding::profile().dim,
+        };
         db.init()?;
         Ok(db)
     }
@@ -123,40 +193,343 @@ impl Database {
                 embedded_text TEXT,
                 last_indexed TEXT
             );
+
+            -- Query → chosen project history (learning from selections)
+            CREATE TABLE IF NOT EXISTS selections (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                query TEXT NOT NULL,
+                shown TEXT NOT NULL,
+                chosen_path TEXT NOT NULL,
+                selected_at TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_selections_query ON selections(query);
+
+            -- Named bookmarks (goto alias set fe ~/code/frontend)
+            CREATE TABLE IF NOT EXISTS aliases (
+                name TEXT PRIMARY KEY,
+                path TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+
+            -- Learned multipliers for ranking boosts
+            CREATE TABLE IF NOT EXISTS ranking_weights (
+                name TEXT PRIMARY KEY,
+                value REAL NOT NULL
+            );
+
+            -- Content-addressed cache of computed embeddings (queries and documents)
+            CREATE TABLE IF NOT EXISTS embedding_cache (
+                model TEXT NOT NULL,
+                text_hash TEXT NOT NULL,
+                embedding BLOB NOT NULL,
+                last_used TEXT NOT NULL,
+                PRIMARY KEY (model, text_hash)
+            );
+
+            -- Owner and field of each vector in project_vectors (same id as its rowid)
+            CREATE TABLE IF NOT EXISTS project_vector_fields (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                project_id INTEGER NOT NULL,
+                field TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_vector_fields_project ON project_vector_fields(project_id);
+
+            -- Symbols declared in each project's largest source files
+            CREATE TABLE IF NOT EXISTS project_symbols (
+                project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+                name TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                file TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_symbols_project ON project_symbols(project_id);
+            CREATE INDEX IF NOT EXISTS idx_symbols_name ON project_symbols(name);
+
+            -- Facts about the index itself (embedding profile, last test run)
+            CREATE TABLE IF NOT EXISTS index_info (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
             "
         )?;
 
+        // Without the semantic feature vec0 isn't loaded, so the legacy table
+        // can't be dropped; it is left for a semantic build to migrate
+        #[cfg(feature = "semantic")]
+        self.drop_legacy_embeddings()?;
+
         // Create vector table for embeddings (vec0 virtual table)
-        // This needs to be done separately as virtual tables have special syntax
+        // This needs to be done separately as virtual tables have special syntax.
+        // Each project has several vectors (one per metadata field, plus README chunks).
+        #[cfg(feature = "semantic")]
+        self.migrate_vector_dimension()?;
+        #[cfg(feature = "semantic")]
         self.conn.execute(
             &format!(
-                "CREATE VIRTUAL TABLE IF NOT EXISTS project_embeddings USING vec0(
-                    project_id INTEGER PRIMARY KEY,
-                    embedding FLOAT[{}]
+                "CREATE VIRTUAL TABLE IF NOT EXISTS project_vectors USING vec0(
+                    embedding FLOAT[{}] distance_metric=cosine
                 )",
-                EMBEDDING_DIM
+                self.dim
             ),
             [],
         )?;
 
+        // Full-text index over project metadata for exact technical terms.
+        // Text is pre-split on camelCase/snake_case by lexical::index_text.
+        self.conn.execute_batch(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS project_fts USING fts5(
+                name, description, keywords, readme, types, tech,
+                tokenize = 'unicode61 remove_diacritics 2'
+            );",
+        )?;
+
+        self.migrate_tech_stack_column()?;
+        self.migrate_parent_column()?;
+        self.migrate_fingerprint_column()?;
+        self.migrate_rejected_column()?;
+        self.backfill_lexical_index()?;
+
+        Ok(())
+    }
+
+    /// Drop the single-vector table used before per-field embeddings.
+    /// The stored profile is marked as outdated, so the projects it held are
+    /// embedded again per field on this run rather than at the next update.
+    #[cfg(feature = "semantic")]
+    fn drop_legacy_embeddings(&self) -> Result<()> {
+        let has_legacy = self
+            .conn
+            .prepare("SELECT 1 FROM sqlite_master WHERE name = 'project_embeddings'")?
+            .exists([])?;
+        if has_legacy {
+            self.conn.execute("DROP TABLE project_embeddings", [])?;
+            self.set_info(semantic::PROFILE_INFO_KEY, "legacy")?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild the vector table when the configured model has another dimension.
+    /// The model and dimension are recorded in index_info; databases from
+    /// before that have their dimension read back from the table definition.
+    #[cfg(feature = "semantic")]
+    fn migrate_vector_dimension(&self) -> Result<()> {
+        let stored_dim = match self.get_info("embedding_dim")? {
+            Some(dim) => dim.parse::<usize>().ok(),
+            None => self.vector_table_dim()?,
+        };
+
+        if stored_dim.is_some_and(|dim| dim != self.dim) {
+            self.conn
+                .execute("DROP TABLE IF EXISTS project_vectors", [])?;
+            self.conn.execute("DELETE FROM project_vector_fields", [])?;
+        }
+
+        self.set_info("embedding_model", &embedding::profile().model_id)?;
+        self.set_info("embedding_dim", &self.dim.to_string())?;
+        Ok(())
+    }
+
+    /// Dimension declared by the existing project_vectors table (`FLOAT[384]`)
+    #[cfg(feature = "semantic")]
+    fn vector_table_dim(&self) -> Result<Option<usize>> {
+        let sql: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE name = 'project_vectors'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(sql.and_then(|sql| {
+            let start = sql.find("FLOAT[")? + "FLOAT[".len();
+            let end = start + sql[start..].find(']')?;
+            sql[start..end].trim().parse().ok()
+        }))
+    }
+
+    /// Fail with an actionable message instead of a sqlite-vec error when a
+    /// vector doesn't fit the index (e.g. it comes from another model)
+    #[cfg(feature = "semantic")]
+    fn check_dimension(&self, vector: &[f32]) -> Result<()> {
+        if vector.len() != self.dim {
+            anyhow::bail!(
+                "Embedding has {} dimensions but the index holds {}-dimensional vectors ({}). \
+                 Check embedding_model in config.toml, then run `goto update --force`",
+                vector.len(),
+                self.dim,
+                embedding::profile().model_id
+            );
+        }
+        Ok(())
+    }
+
+    /// Add the parent_id column (workspace membership) to older databases
+    fn migrate_parent_column(&self) -> Result<()> {
+        let has_column = self
+            .conn
+            .prepare("SELECT 1 FROM pragma_table_info('projects') WHERE name = 'parent_id'")?
+            .exists([])?;
+        if !has_column {
+            self.conn.execute(
+                "ALTER TABLE projects ADD COLUMN parent_id INTEGER REFERENCES projects(id)",
+                [],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Add the fingerprint column (change detection for `goto update`) to older databases
+    fn migrate_fingerprint_column(&self) -> Result<()> {
+        let has_column = self
+            .conn
+            .prepare(
+                "SELECT 1 FROM pragma_table_info('project_metadata') WHERE name = 'fingerprint'",
+            )?
+            .exists([])?;
+        if !has_column {
+            self.conn.execute(
+                "ALTER TABLE project_metadata ADD COLUMN fingerprint TEXT",
+                [],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Add the rejected column (jumps followed by an immediate new query) to older databases
+    fn migrate_rejected_column(&self) -> Result<()> {
+        let has_column = self
+            .conn
+            .prepare("SELECT 1 FROM pragma_table_info('selections') WHERE name = 'rejected'")?
+            .exists([])?;
+        if !has_column {
+            self.conn.execute(
+                "ALTER TABLE selections ADD COLUMN rejected INTEGER NOT NULL DEFAULT 0",
+                [],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Add the tech_stack column to older databases, recovered from embedded_text
+    fn migrate_tech_stack_column(&self) -> Result<()> {
+        let has_column = self
+            .conn
+            .prepare(
+                "SELECT 1 FROM pragma_table_info('project_metadata') WHERE name = 'tech_stack'",
+            )?
+            .exists([])?;
+        if has_column {
+            return Ok(());
+        }
+
+        self.conn.execute(
+            "ALTER TABLE project_metadata ADD COLUMN tech_stack TEXT",
+            [],
+        )?;
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT project_id, embedded_text FROM project_metadata")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, Option<String>>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (id, embedded_text) in rows {
+            if let Some(tech) = embedded_text
+                .as_deref()
+                .and_then(|t| embedded_section(t, "Technologies: "))
+            {
+                self.conn.execute(
+                    "UPDATE project_metadata SET tech_stack = ?1 WHERE project_id = ?2",
+                    params![tech, id],
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Populate the full-text index for projects indexed before it existed
+    fn backfill_lexical_index(&self) -> Result<()> {
+        let mut stmt = self.conn.prepare(
+            "SELECT p.id, p.name, pm.description, pm.readme_excerpt, pm.embedded_text
+             FROM project_metadata pm
+             JOIN projects p ON p.id = pm.project_id
+             WHERE pm.project_id NOT IN (SELECT rowid FROM project_fts)",
+        )?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (id, name, description, readme, embedded_text) in rows {
+            // Recover tagged sections from the embedded text ("Types: A, B | ...")
+            let text = embedded_text.as_deref().unwrap_or("");
+            let types: Vec<String> = ["Types: ", "Structure: "]
+                .iter()
+                .filter_map(|prefix| embedded_section(text, prefix))
+                .map(String::from)
+                .collect();
+            let tech: Vec<String> = embedded_section(text, "Technologies: ")
+                .map(|t| vec![t.to_string()])
+                .unwrap_or_default();
+
+            self.upsert_lexical(
+                id,
+                &LexicalFields {
+                    name: &name,
+                    description: description.as_deref(),
+                    keywords: &[],
+                    readme: readme.as_deref(),
+                    types: &types,
+                    tech: &tech,
+                },
+            )?;
+        }
+
         Ok(())
     }
 
+    /// Start a transaction: writes made through this Database until it is
+    /// committed are applied together (or not at all if it's dropped)
+    pub fn begin(&self) -> Result<Transaction<'_>> {
+        Ok(self.conn.unchecked_transaction()?)
+    }
+
     /// Batch insert/update projects in a single transaction
-    pub fn upsert_projects_batch(&mut self, paths: &[PathBuf], source: ProjectSource) -> Result<usize> {
+    pub fn upsert_projects_batch(
+        &mut self,
+        paths: &[PathBuf],
+        source: ProjectSource,
+    ) -> Result<usize> {
         let tx = self.conn.transaction()?;
         let count = Self::upsert_in_transaction(&tx, paths, source)?;
         tx.commit()?;
         Ok(count)
     }
 
-    fn upsert_in_transaction(tx: &Transaction, paths: &[PathBuf], source: ProjectSource) -> Result<usize> {
+    fn upsert_in_transaction(
+        tx: &Transaction,
+        paths: &[PathBuf],
+        source: ProjectSource,
+    ) -> Result<usize> {
         let mut stmt = tx.prepare(
             "INSERT INTO projects (path, name, last_accessed, access_count, last_modified, source)
              VALUES (?1, ?2, ?3, 0, ?4, ?5)
              ON CONFLICT(path) DO UPDATE SET
                  last_modified = ?4,
-                 source = CASE WHEN source = 'manual' THEN 'manual' ELSE ?5 END"
+                 source = CASE WHEN source = 'manual' THEN 'manual' ELSE ?5 END",
         )?;
 
         let now = Utc::now().to_rfc3339();
@@ -171,7 +544,7 @@ impl Database {
 
             let last_modified = std::fs::metadata(path)
                 .and_then(|m| m.modified())
-                .map(|t| DateTime::<Utc>::from(t))
+                .map(DateTime::<Utc>::from)
                 .unwrap_or_else(|_| Utc::now())
                 .to_rfc3339();
 
@@ -188,8 +561,32 @@ impl Database {
         Ok(count)
     }
 
+    /// Insert/update the members of a workspace and link them to its root
+    pub fn upsert_members(
+        &mut self,
+        parent: &Path,
+        members: &[PathBuf],
+        source: ProjectSource,
+    ) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let count = Self::upsert_in_transaction(&tx, members, source)?;
+        {
+            let mut stmt = tx.prepare(
+                "UPDATE projects SET parent_id = (SELECT id FROM projects WHERE path = ?1) WHERE path = ?2",
+            )?;
+            for member in members {
+                stmt.execute(params![
+                    parent.to_string_lossy().as_ref(),
+                    member.to_string_lossy().as_ref(),
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(count)
+    }
+
     /// Mark a project as accessed (increment count and update timestamp)
-    pub fn mark_accessed(&self, path: &PathBuf) -> Result<()> {
+    pub fn mark_accessed(&self, path: &Path) -> Result<()> {
         let now = Utc::now().to_rfc3339();
         self.conn.execute(
             "UPDATE projects SET last_accessed = ?1, access_count = access_count + 1 WHERE path = ?2",
@@ -201,7 +598,9 @@ impl Database {
     /// Get all projects
     pub fn get_all_projects(&self) -> Result<Vec<Project>> {
         let mut stmt = self.conn.prepare(
-            "SELECT path, name, last_accessed, access_count, source FROM projects"
+            "SELECT p.path, p.name, p.last_accessed, p.access_count, p.source, parent.path
+             FROM projects p
+             LEFT JOIN projects parent ON parent.id = p.parent_id",
         )?;
 
         let projects = stmt.query_map([], |row| {
@@ -212,9 +611,11 @@ impl Database {
                     .map(|dt| dt.with_timezone(&Utc))
                     .unwrap_or_else(|_| Utc::now()),
                 access_count: row.get(3)?,
-                source: row.get::<_, String>(4)?
+                source: row
+                    .get::<_, String>(4)?
                     .parse()
                     .unwrap_or(ProjectSource::Scan),
+                parent: row.get::<_, Option<String>>(5)?.map(PathBuf::from),
             })
         })?;
 
@@ -247,9 +648,28 @@ impl Database {
         // Batch delete in single transaction
         let tx = self.conn.transaction()?;
         {
+            // Members still point at a removed workspace root until they are removed too
+            let mut detach_stmt =
+                tx.prepare("UPDATE projects SET parent_id = NULL WHERE parent_id = ?")?;
             let mut delete_stmt = tx.prepare("DELETE FROM projects WHERE id = ?")?;
+            let mut delete_fts_stmt = tx.prepare("DELETE FROM project_fts WHERE rowid = ?")?;
+            #[cfg(feature = "semantic")]
+            let mut delete_vectors_stmt = tx.prepare(
+                "DELETE FROM project_vectors WHERE rowid IN
+                    (SELECT id FROM project_vector_fields WHERE project_id = ?)",
+            )?;
+            let mut delete_fields_stmt =
+                tx.prepare("DELETE FROM project_vector_fields WHERE project_id = ?")?;
+            let mut delete_symbols_stmt =
+                tx.prepare("DELETE FROM project_symbols WHERE project_id = ?")?;
             for id in &missing_ids {
+                detach_stmt.execute([id])?;
                 delete_stmt.execute([id])?;
+                delete_fts_stmt.execute([id])?;
+                #[cfg(feature = "semantic")]
+                delete_vectors_stmt.execute([id])?;
+                delete_fields_stmt.execute([id])?;
+                delete_symbols_stmt.execute([id])?;
             }
         }
         tx.commit()?;
@@ -265,24 +685,211 @@ impl Database {
         project_id: i64,
         description: Option<&str>,
         readme_excerpt: Option<&str>,
+        tech_stack: &[String],
         embedded_text: &str,
+        fingerprint: &str,
     ) -> Result<()> {
         let now = Utc::now().to_rfc3339();
         self.conn.execute(
-            "INSERT INTO project_metadata (project_id, description, readme_excerpt, embedded_text, last_indexed)
-             VALUES (?1, ?2, ?3, ?4, ?5)
+            "INSERT INTO project_metadata (project_id, description, readme_excerpt, tech_stack, embedded_text, last_indexed, fingerprint)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
              ON CONFLICT(project_id) DO UPDATE SET
                  description = ?2,
                  readme_excerpt = ?3,
-                 embedded_text = ?4,
-                 last_indexed = ?5",
-            params![project_id, description, readme_excerpt, embedded_text, now],
+                 tech_stack = ?4,
+                 embedded_text = ?5,
+                 last_indexed = ?6,
+                 fingerprint = ?7",
+            params![project_id, description, readme_excerpt, tech_stack.join(", "), embedded_text, now, fingerprint],
         )?;
         Ok(())
     }
 
+    /// Fingerprint of the files each indexed project was extracted from
+    pub fn get_fingerprints(&self) -> Result<Vec<FingerprintRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT p.id, p.path, p.name, pm.fingerprint
+             FROM projects p
+             JOIN project_metadata pm ON pm.project_id = p.id
+             WHERE EXISTS (SELECT 1 FROM {} f WHERE f.project_id = p.id)",
+            INDEXED_TABLE
+        ))?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                PathBuf::from(row.get::<_, String>(1)?),
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })?;
+
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Record the fingerprint of a project without re-extracting it
+    pub fn set_fingerprint(&self, project_id: i64, fingerprint: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE project_metadata SET fingerprint = ?1 WHERE project_id = ?2",
+            params![fingerprint, project_id],
+        )?;
+        Ok(())
+    }
+
+    /// Get the detected tech stack of every indexed project, keyed by path
+    pub fn get_tech_stacks(&self) -> Result<HashMap<PathBuf, Vec<String>>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT p.path, pm.tech_stack FROM project_metadata pm
+             JOIN projects p ON pm.project_id = p.id
+             WHERE pm.tech_stack IS NOT NULL",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            let tech: String = row.get(1)?;
+            Ok((
+                PathBuf::from(row.get::<_, String>(0)?),
+                tech.split(", ")
+                    .filter(|t| !t.is_empty())
+                    .map(String::from)
+                    .collect(),
+            ))
+        })?;
+
+        rows.collect::<Result<HashMap<_, _>, _>>()
+            .map_err(Into::into)
+    }
+
+    /// Description, keywords and tech stack of every indexed project, keyed by
+    /// path (as indexed for full-text search, with identifiers split)
+    pub fn get_exclusion_metadata(&self) -> Result<HashMap<PathBuf, String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT p.path, f.description, f.keywords, f.tech FROM project_fts f
+             JOIN projects p ON p.id = f.rowid",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                PathBuf::from(row.get::<_, String>(0)?),
+                format!(
+                    "{} {} {}",
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?
+                ),
+            ))
+        })?;
+
+        rows.collect::<Result<HashMap<_, _>, _>>()
+            .map_err(Into::into)
+    }
+
+    /// Replace the symbols stored for a project
+    pub fn replace_symbols(&self, project_id: i64, symbols: &[Symbol]) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM project_symbols WHERE project_id = ?",
+            [project_id],
+        )?;
+        let mut stmt = self.conn.prepare_cached(
+            "INSERT INTO project_symbols (project_id, name, kind, file) VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for symbol in symbols {
+            stmt.execute(params![
+                project_id,
+                symbol.name,
+                symbol.kind.as_str(),
+                symbol.file.to_string_lossy()
+            ])?;
+        }
+        Ok(())
+    }
+
+    /// Symbols stored for a project, in extraction order
+    pub fn get_symbols(&self, path: &Path) -> Result<Vec<Symbol>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT s.name, s.kind, s.file FROM project_symbols s
+             JOIN projects p ON p.id = s.project_id
+             WHERE p.path = ?
+             ORDER BY s.rowid",
+        )?;
+
+        let rows = stmt.query_map([path.to_string_lossy()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut symbols = Vec::new();
+        for row in rows {
+            let (name, kind, file) = row?;
+            if let Some(kind) = SymbolKind::parse(&kind) {
+                symbols.push(Symbol {
+                    name,
+                    kind,
+                    file: PathBuf::from(file),
+                });
+            }
+        }
+        Ok(symbols)
+    }
+
+    /// Store or update the full-text index entry for a project
+    pub fn upsert_lexical(&self, project_id: i64, fields: &LexicalFields) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM project_fts WHERE rowid = ?", [project_id])?;
+        self.conn.execute(
+            "INSERT INTO project_fts (rowid, name, description, keywords, readme, types, tech)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                project_id,
+                lexical::index_text(fields.name),
+                lexical::index_text(fields.description.unwrap_or("")),
+                lexical::index_text(&fields.keywords.join(" ")),
+                lexical::index_text(fields.readme.unwrap_or("")),
+                lexical::index_text(&fields.types.join(" ")),
+                lexical::index_text(&fields.tech.join(" ")),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Full-text search over project metadata
+    /// Returns project IDs with their indexed columns (name, description,
+    /// keywords, readme, types, tech), ordered by BM25 relevance (best first)
+    pub fn find_lexical(&self, match_expr: &str, limit: usize) -> Result<Vec<(i64, [String; 6])>> {
+        // Column weights: name, description, keywords, readme, types, tech
+        let mut stmt = self.conn.prepare(
+            "SELECT rowid, name, description, keywords, readme, types, tech
+             FROM project_fts
+             WHERE project_fts MATCH ?
+             ORDER BY bm25(project_fts, 10.0, 5.0, 5.0, 1.0, 3.0, 2.0)
+             LIMIT ?",
+        )?;
+
+        let results = stmt.query_map(params![match_expr, limit as i64], |row| {
+            let column = |i| {
+                row.get::<_, Option<String>>(i)
+                    .map(Option::unwrap_or_default)
+            };
+            Ok((
+                row.get::<_, i64>(0)?,
+                [
+                    column(1)?,
+                    column(2)?,
+                    column(3)?,
+                    column(4)?,
+                    column(5)?,
+                    column(6)?,
+                ],
+            ))
+        })?;
+
+        results.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
     /// Get embedded_text for a project by path (used for metadata-based boosting)
-    pub fn get_embedded_text(&self, path: &std::path::Path) -> Result<Option<String>> {
+    pub fn get_embedded_text(&self, path: &Path) -> Result<Option<String>> {
         let mut stmt = self.conn.prepare(
             "SELECT pm.embedded_text FROM project_metadata pm
              JOIN projects p ON pm.project_id = p.id
@@ -294,35 +901,133 @@ impl Database {
         Ok(result)
     }
 
-    /// Store embedding for a project
-    pub fn upsert_embedding(&self, project_id: i64, embedding: &[f32]) -> Result<()> {
-        // Delete existing embedding if any
+    /// Replace the embeddings of a project, one (field, vector) pair per entry.
+    /// Vectors are normalized to unit length.
+    #[cfg(feature = "semantic")]
+    pub fn upsert_embeddings(&self, project_id: i64, embeddings: &[(&str, &[f32])]) -> Result<()> {
+        for (_, vector) in embeddings {
+            self.check_dimension(vector)?;
+        }
+        self.delete_embeddings(project_id)?;
+
+        for (field, vector) in embeddings {
+            self.conn.execute(
+                "INSERT INTO project_vector_fields (project_id, field) VALUES (?, ?)",
+                params![project_id, field],
+            )?;
+            let vector_id = self.conn.last_insert_rowid();
+            self.conn.execute(
+                "INSERT INTO project_vectors (rowid, embedding) VALUES (?, ?)",
+                params![vector_id, embedding::normalize(vector).as_bytes()],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Remove the vectors of a project, which then counts as unindexed
+    #[cfg(feature = "semantic")]
+    pub fn delete_embeddings(&self, project_id: i64) -> Result<()> {
         self.conn.execute(
-            "DELETE FROM project_embeddings WHERE project_id = ?",
+            "DELETE FROM project_vectors WHERE rowid IN
+                (SELECT id FROM project_vector_fields WHERE project_id = ?)",
             [project_id],
         )?;
-
-        // Insert new embedding
         self.conn.execute(
-            "INSERT INTO project_embeddings (project_id, embedding) VALUES (?, ?)",
-            params![project_id, embedding.as_bytes()],
+            "DELETE FROM project_vector_fields WHERE project_id = ?",
+            [project_id],
         )?;
         Ok(())
     }
 
-    /// Find most similar projects to a query embedding
-    /// Returns (project_id, distance) pairs sorted by similarity (lower distance = more similar)
-    pub fn find_similar(&self, query_embedding: &[f32], limit: usize) -> Result<Vec<(i64, f32)>> {
+    /// Look up cached embeddings by text hash (marks the hits as used)
+    #[cfg(feature = "semantic")]
+    pub fn get_cached_embeddings(
+        &self,
+        model: &str,
+        hashes: &[String],
+    ) -> Result<HashMap<String, Vec<f32>>> {
+        let now = Utc::now().to_rfc3339();
+        let tx = self.conn.unchecked_transaction()?;
+        let mut found = HashMap::new();
+        {
+            let mut select = tx.prepare(
+                "SELECT embedding FROM embedding_cache WHERE model = ?1 AND text_hash = ?2",
+            )?;
+            let mut touch = tx.prepare(
+                "UPDATE embedding_cache SET last_used = ?3 WHERE model = ?1 AND text_hash = ?2",
+            )?;
+            for hash in hashes {
+                let bytes: Option<Vec<u8>> = select
+                    .query_row(params![model, hash], |row| row.get(0))
+                    .optional()?;
+                if let Some(bytes) = bytes {
+                    touch.execute(params![model, hash, &now])?;
+                    found.insert(hash.clone(), vector_from_bytes(&bytes));
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(found)
+    }
+
+    /// Store computed embeddings in the cache
+    #[cfg(feature = "semantic")]
+    pub fn put_cached_embeddings(&self, model: &str, entries: &[(&str, &[f32])]) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let tx = self.conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO embedding_cache (model, text_hash, embedding, last_used)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (hash, vector) in entries {
+                stmt.execute(params![model, hash, vector.as_bytes(), &now])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Keep only the `max_entries` most recently used cached embeddings
+    #[cfg(feature = "semantic")]
+    pub fn prune_embedding_cache(&self, max_entries: usize) -> Result<usize> {
+        let removed = self.conn.execute(
+            "DELETE FROM embedding_cache WHERE rowid NOT IN
+                (SELECT rowid FROM embedding_cache ORDER BY last_used DESC LIMIT ?)",
+            [max_entries as i64],
+        )?;
+        Ok(removed)
+    }
+
+    /// Find the vectors most similar to a query embedding
+    /// Returns (project_id, field, cosine distance) sorted by distance; a project
+    /// can appear once per matching field
+    #[cfg(feature = "semantic")]
+    pub fn find_similar(
+        &self,
+        query_embedding: &[f32],
+        limit: usize,
+    ) -> Result<Vec<(i64, String, f32)>> {
+        self.check_dimension(query_embedding)?;
         let mut stmt = self.conn.prepare(
-            "SELECT project_id, distance
-             FROM project_embeddings
-             WHERE embedding MATCH ?
-             ORDER BY distance
-             LIMIT ?",
+            "WITH knn AS (
+                SELECT rowid, distance
+                FROM project_vectors
+                WHERE embedding MATCH ? AND k = ?
+             )
+             SELECT f.project_id, f.field, knn.distance
+             FROM knn
+             JOIN project_vector_fields f ON f.id = knn.rowid
+             ORDER BY knn.distance",
         )?;
 
+        let query_embedding = embedding::normalize(query_embedding);
         let results = stmt.query_map(params![query_embedding.as_bytes(), limit as i64], |row| {
-            Ok((row.get::<_, i64>(0)?, row.get::<_, f32>(1)?))
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, f32>(2)?,
+            ))
         })?;
 
         results.collect::<Result<Vec<_>, _>>().map_err(Into::into)
@@ -330,12 +1035,12 @@ impl Database {
 
     /// Get projects that don't have embeddings yet
     pub fn get_unindexed_projects(&self) -> Result<Vec<(i64, PathBuf, String)>> {
-        let mut stmt = self.conn.prepare(
+        let mut stmt = self.conn.prepare(&format!(
             "SELECT p.id, p.path, p.name
              FROM projects p
-             LEFT JOIN project_embeddings e ON p.id = e.project_id
-             WHERE e.project_id IS NULL",
-        )?;
+             WHERE NOT EXISTS (SELECT 1 FROM {} f WHERE f.project_id = p.id)",
+            INDEXED_TABLE
+        ))?;
 
         let results = stmt.query_map([], |row| {
             Ok((
@@ -351,7 +1056,10 @@ impl Database {
     /// Get project by ID
     pub fn get_project_by_id(&self, id: i64) -> Result<Option<Project>> {
         let mut stmt = self.conn.prepare(
-            "SELECT path, name, last_accessed, access_count, source FROM projects WHERE id = ?",
+            "SELECT p.path, p.name, p.last_accessed, p.access_count, p.source, parent.path
+             FROM projects p
+             LEFT JOIN projects parent ON parent.id = p.parent_id
+             WHERE p.id = ?",
         )?;
 
         let result = stmt
@@ -363,9 +1071,11 @@ impl Database {
                         .map(|dt| dt.with_timezone(&Utc))
                         .unwrap_or_else(|_| Utc::now()),
                     access_count: row.get(3)?,
-                    source: row.get::<_, String>(4)?
+                    source: row
+                        .get::<_, String>(4)?
                         .parse()
                         .unwrap_or(ProjectSource::Scan),
+                    parent: row.get::<_, Option<String>>(5)?.map(PathBuf::from),
                 })
             })
             .optional()?;
@@ -375,21 +1085,221 @@ impl Database {
 
     /// Clear all embeddings (for re-indexing)
     pub fn clear_embeddings(&self) -> Result<()> {
-        self.conn.execute("DELETE FROM project_embeddings", [])?;
+        #[cfg(feature = "semantic")]
+        self.conn.execute("DELETE FROM project_vectors", [])?;
+        self.conn.execute("DELETE FROM project_vector_fields", [])?;
         self.conn.execute("DELETE FROM project_metadata", [])?;
+        self.conn.execute("DELETE FROM project_symbols", [])?;
+        self.conn.execute("DELETE FROM project_fts", [])?;
         Ok(())
     }
 
+    /// Drop all vectors but keep the extracted metadata.
+    /// Every project shows up as unindexed and is embedded again.
+    #[cfg(feature = "semantic")]
+    pub fn invalidate_embeddings(&self) -> Result<()> {
+        self.conn.execute("DELETE FROM project_vectors", [])?;
+        self.conn.execute("DELETE FROM project_vector_fields", [])?;
+        Ok(())
+    }
+
+    /// Read a value from the index_info table
+    pub fn get_info(&self, key: &str) -> Result<Option<String>> {
+        let value = self
+            .conn
+            .query_row("SELECT value FROM index_info WHERE key = ?", [key], |row| {
+                row.get(0)
+            })
+            .optional()?;
+        Ok(value)
+    }
+
+    /// Store a value in the index_info table
+    pub fn set_info(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO index_info (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = ?2",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Remove a value from the index_info table
+    pub fn remove_info(&self, key: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM index_info WHERE key = ?", [key])?;
+        Ok(())
+    }
+
+    // ========== Alias Methods ==========
+
+    /// Create or update an alias
+    pub fn set_alias(&self, name: &str, path: &Path) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO aliases (name, path, created_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(name) DO UPDATE SET path = ?2",
+            params![
+                name,
+                path.to_string_lossy().as_ref(),
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Remove an alias, returning whether it existed
+    pub fn remove_alias(&self, name: &str) -> Result<bool> {
+        let removed = self
+            .conn
+            .execute("DELETE FROM aliases WHERE name = ?", [name])?;
+        Ok(removed > 0)
+    }
+
+    /// Resolve an alias to its target path
+    pub fn get_alias(&self, name: &str) -> Result<Option<PathBuf>> {
+        let result = self
+            .conn
+            .query_row("SELECT path FROM aliases WHERE name = ?", [name], |row| {
+                row.get::<_, String>(0)
+            })
+            .optional()?;
+        Ok(result.map(PathBuf::from))
+    }
+
+    /// Get all aliases as (name, path), sorted by name
+    pub fn get_aliases(&self) -> Result<Vec<(String, PathBuf)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name, path FROM aliases ORDER BY name")?;
+        let results = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                PathBuf::from(row.get::<_, String>(1)?),
+            ))
+        })?;
+        results.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    // ========== Learning Methods ==========
+
+    /// Record a query, the candidates shown for it and the project chosen
+    pub fn insert_selection(&self, query: &str, shown: &[&Path], chosen: &Path) -> Result<()> {
+        let shown: Vec<String> = shown
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        self.conn.execute(
+            "INSERT INTO selections (query, shown, chosen_path, selected_at) VALUES (?1, ?2, ?3, ?4)",
+            params![query, shown.join("\n"), chosen.to_string_lossy().as_ref(), Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Record that the project jumped to for a query was not the one wanted
+    pub fn insert_rejection(&self, query: &str, path: &Path) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO selections (query, shown, chosen_path, selected_at, rejected) VALUES (?1, '', ?2, ?3, 1)",
+            params![query, path.to_string_lossy().as_ref(), Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Past selections and rejections for a query as (path, when, rejected)
+    pub fn get_selections(&self, query: &str) -> Result<Vec<(PathBuf, DateTime<Utc>, bool)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT chosen_path, selected_at, rejected FROM selections WHERE query = ?")?;
+
+        let results = stmt.query_map([query], |row| {
+            Ok((
+                PathBuf::from(row.get::<_, String>(0)?),
+                DateTime::parse_from_rfc3339(&row.get::<_, String>(1)?)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                row.get::<_, bool>(2)?,
+            ))
+        })?;
+
+        results.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Learned (query, chosen path, count) preferences, most frequent first
+    pub fn get_learned_queries(&self, limit: usize) -> Result<Vec<(String, PathBuf, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT query, chosen_path, COUNT(*) AS n FROM selections
+             WHERE rejected = 0
+             GROUP BY query, chosen_path
+             ORDER BY n DESC, MAX(selected_at) DESC
+             LIMIT ?",
+        )?;
+
+        let results = stmt.query_map([limit as i64], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                PathBuf::from(row.get::<_, String>(1)?),
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+
+        results.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Get learned ranking weights as (name, value) pairs
+    pub fn get_ranking_weights(&self) -> Result<Vec<(String, f32)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name, value FROM ranking_weights")?;
+        let results = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)? as f32))
+        })?;
+        results.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Store learned ranking weights
+    pub fn set_ranking_weights(&self, weights: &[(&str, f32)]) -> Result<()> {
+        let mut stmt = self.conn.prepare(
+            "INSERT INTO ranking_weights (name, value) VALUES (?1, ?2)
+             ON CONFLICT(name) DO UPDATE SET value = ?2",
+        )?;
+        for (name, value) in weights {
+            stmt.execute(params![name, *value as f64])?;
+        }
+        Ok(())
+    }
+
+    /// Forget all selections and learned weights
+    pub fn clear_learning(&self) -> Result<usize> {
+        let removed = self.conn.execute("DELETE FROM selections", [])?;
+        self.conn.execute("DELETE FROM ranking_weights", [])?;
+        Ok(removed)
+    }
+
     /// Get embedding statistics
     pub fn embedding_stats(&self) -> Result<(usize, usize)> {
         let total: usize = self
             .conn
             .query_row("SELECT COUNT(*) FROM projects", [], |row| row.get(0))?;
         let indexed: usize = self.conn.query_row(
-            "SELECT COUNT(*) FROM project_embeddings",
+            &format!("SELECT COUNT(DISTINCT project_id) FROM {}", INDEXED_TABLE),
             [],
             |row| row.get(0),
         )?;
         Ok((indexed, total))
     }
 }
+
+/// Decode a vector stored as raw f32 bytes
+#[cfg(feature = "semantic")]
+fn vector_from_bytes(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+/// Find a tagged section ("Technologies: Rust, Docker") in an embedded text
+fn embedded_section<'a>(embedded_text: &'a str, prefix: &str) -> Option<&'a str> {
+    embedded_text
+        .split(" | ")
+        .find_map(|section| section.strip_prefix(prefix))
+}
@@ -1,12 +1,252 @@
-use anyhow::{Context, Result};
+#[cfg(feature = "semantic-local")]
+use anyhow::Context;
+use anyhow::Result;
+#[cfg(feature = "semantic-local")]
 use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, OnceLock};
 
-use crate::config::Config;
+#[cfg(feature = "semantic-local")]
+use crate::config::DEFAULT_EMBEDDING_MODEL;
+use crate::config::{Config, EmbeddingBackendKind};
+#[cfg(feature = "semantic-http")]
+use crate::config::{EmbeddingServer, DEFAULT_SERVER_MODEL};
+#[cfg(feature = "semantic-http")]
+use crate::embedding_http::HttpBackend;
 
-/// Vector dimension for AllMiniLML6V2 model
-pub const EMBEDDING_DIM: usize = 384;
+/// Something that turns texts into vectors
+pub trait EmbeddingBackend: Send {
+    /// Embed a batch of texts, one vector per text, in order
+    fn embed(&mut self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+}
+
+/// Where the vectors of a profile are computed
+pub enum ModelSource {
+    /// In-process ONNX model, downloaded on first use
+    #[cfg(feature = "semantic-local")]
+    Fastembed(EmbeddingModel),
+    /// Local embedding server (Ollama or OpenAI-compatible), with the model
+    /// name exactly as the server knows it
+    #[cfg(feature = "semantic-http")]
+    Http { url: String, model: String },
+}
+
+/// The embedding model in use and how texts are formatted for it
+pub struct ModelProfile {
+    /// Identifier of the embedding model (lowercase), part of the embedding cache key
+    pub model_id: String,
+    /// Vector dimension produced by the model
+    pub dim: usize,
+    /// Bumped whenever the formatting below changes, so indexes get re-embedded
+    pub revision: u32,
+    /// Instruction prepended to search queries
+    pub query_prefix: &'static str,
+    /// Instruction prepended to indexed documents
+    pub passage_prefix: &'static str,
+    /// Longest text (prefix included) sent to the model (~512 tokens)
+    pub max_chars: usize,
+    /// Cosine similarity scored 50% by `calibrated_score`
+    pub similarity_midpoint: f32,
+    /// Spread of the logistic curve around the midpoint
+    pub similarity_scale: f32,
+    pub source: ModelSource,
+}
+
+impl ModelProfile {
+    /// Profile of a fastembed model, by its code (`intfloat/multilingual-e5-small`)
+    /// or the part after the slash (`multilingual-e5-small`)
+    #[cfg(feature = "semantic-local")]
+    pub fn for_model(name: &str) -> Result<Self> {
+        let info = TextEmbedding::list_supported_models()
+            .into_iter()
+            .find(|m| {
+                m.model_code.eq_ignore_ascii_case(name)
+                    || short_model_id(&m.model_code).eq_ignore_ascii_case(name)
+            })
+            .with_context(|| {
+                format!("Unknown embedding model '{}' (see https://github.com/Anush008/fastembed-rs for the list)", name)
+            })?;
+
+        let (query_prefix, passage_prefix) = instruction_prefixes(&info.model_code);
+        let (similarity_midpoint, similarity_scale) = similarity_calibration(&info.model_code);
+        Ok(Self {
+            model_id: short_model_id(&info.model_code),
+            dim: info.dim,
+            revision: 1,
+            query_prefix,
+            passage_prefix,
+            max_chars: 2000,
+            similarity_midpoint,
+            similarity_scale,
+            source: ModelSource::Fastembed(info.model),
+        })
+    }
+
+    /// Profile of a model served over HTTP. The server can't be asked for the
+    /// dimension without embedding something, so the config provides it.
+    #[cfg(feature = "semantic-http")]
+    pub fn for_server(name: &str, url: &str, dim: usize) -> Self {
+        let (query_prefix, passage_prefix) = instruction_prefixes(name);
+        let (similarity_midpoint, similarity_scale) = similarity_calibration(name);
+        Self {
+            model_id: name.to_lowercase(),
+            dim,
+            revision: 1,
+            query_prefix,
+            passage_prefix,
+            max_chars: 2000,
+            similarity_midpoint,
+            similarity_scale,
+            source: ModelSource::Http {
+                url: url.to_string(),
+                model: name.to_string(),
+            },
+        }
+    }
+
+    /// Identifies the vectors this profile produces (model + formatting)
+    pub fn key(&self) -> String {
+        format!("{}@{}", self.model_id, self.revision)
+    }
+
+    /// Text to embed for a search query
+    pub fn format_query(&self, query: &str) -> String {
+        self.format(self.query_prefix, query)
+    }
+
+    /// Text to embed for an indexed document
+    pub fn format_passage(&self, passage: &str) -> String {
+        self.format(self.passage_prefix, passage)
+    }
+
+    fn format(&self, prefix: &str, text: &str) -> String {
+        let budget = self.max_chars.saturating_sub(prefix.chars().count());
+        format!("{}{}", prefix, truncate_at_word(text.trim(), budget))
+    }
+
+    /// Map a cosine distance to a calibrated confidence score (0-100)
+    ///
+    /// Unlike 1/(1+d), the result is comparable across queries: the same
+    /// percentage means the same cosine similarity whatever was asked.
+    pub fn calibrated_score(&self, distance: f32) -> f32 {
+        let similarity = 1.0 - distance;
+        100.0 / (1.0 + (-(similarity - self.similarity_midpoint) / self.similarity_scale).exp())
+    }
+}
+
+/// Query and passage instructions a model was trained with.
+/// Such models produce noticeably worse vectors without them.
+fn instruction_prefixes(model: &str) -> (&'static str, &'static str) {
+    let model = model.to_lowercase();
+    if model.contains("e5") {
+        ("query: ", "passage: ")
+    } else if model.contains("nomic-embed") {
+        ("search_query: ", "search_document: ")
+    } else if model.contains("bge") && model.contains("-en") {
+        (
+            "Represent this sentence for searching relevant passages: ",
+            "",
+        )
+    } else {
+        ("", "")
+    }
+}
+
+/// Cosine similarity that maps to a 50% score, and the spread around it
+///
+/// Models differ in how close unrelated texts look. E5 embeddings are
+/// anisotropic: unrelated texts still score ~0.75 and good matches 0.85+
+/// (0.80 → 50%, 0.85 → 84%, 0.90 → 96%). BGE, GTE, Nomic and MiniLM
+/// vectors spread over a wider range, with lower midpoints.
+fn similarity_calibration(model: &str) -> (f32, f32) {
+    let model = model.to_lowercase();
+    if model.contains("e5") {
+        (0.80, 0.03)
+    } else if model.contains("bge") || model.contains("gte") {
+        (0.62, 0.05)
+    } else if model.contains("nomic-embed") || model.contains("mxbai") {
+        (0.55, 0.05)
+    } else if model.contains("minilm") || model.contains("mpnet") {
+        (0.40, 0.08)
+    } else {
+        (0.50, 0.08)
+    }
+}
+
+/// `intfloat/multilingual-e5-small` → `multilingual-e5-small`
+#[cfg(feature = "semantic-local")]
+fn short_model_id(model_code: &str) -> String {
+    model_code
+        .rsplit('/')
+        .next()
+        .unwrap_or(model_code)
+        .to_lowercase()
+}
+
+/// Profile selected with `configure` (the default model until then)
+static PROFILE: OnceLock<ModelProfile> = OnceLock::new();
+
+/// Select the embedding backend and model from the config. Must be called
+/// before the model or the database is used; later calls are ignored.
+pub fn configure(config: &Config) -> Result<()> {
+    let profile = match config.embedding_backend {
+        #[cfg(feature = "semantic-local")]
+        EmbeddingBackendKind::Fastembed => ModelProfile::for_model(&config.embedding_model)?,
+        #[cfg(not(feature = "semantic-local"))]
+        EmbeddingBackendKind::Fastembed => anyhow::bail!(
+            "This build has no in-process embedding models (built without semantic-local): \
+             set embedding_backend = \"http\" and an [embedding_server] section"
+        ),
+        #[cfg(feature = "semantic-http")]
+        EmbeddingBackendKind::Http => {
+            let server = match &config.embedding_server {
+                Some(server) => server.clone(),
+                // Ollama with its usual embedding model needs no setup
+                None if config.embedding_model == DEFAULT_SERVER_MODEL => EmbeddingServer::default(),
+                None => anyhow::bail!(
+                    "embedding_backend = \"http\" needs an [embedding_server] section with url and dim"
+                ),
+            };
+            ModelProfile::for_server(&config.embedding_model, &server.url, server.dim)
+        }
+        #[cfg(not(feature = "semantic-http"))]
+        EmbeddingBackendKind::Http => anyhow::bail!(
+            "This build can't use an embedding server (built without semantic-http): \
+             set embedding_backend = \"fastembed\""
+        ),
+    };
+    let _ = PROFILE.set(profile);
+    Ok(())
+}
+
+/// Profile of the embedding model in use
+#[cfg(feature = "semantic-local")]
+pub fn profile() -> &'static ModelProfile {
+    PROFILE.get_or_init(|| {
+        ModelProfile::for_model(DEFAULT_EMBEDDING_MODEL)
+            .expect("default embedding model is supported")
+    })
+}
+
+/// Profile of the embedding model in use (builds without local models have
+/// no default model to fall back on before `configure`)
+#[cfg(not(feature = "semantic-local"))]
+pub fn profile() -> &'static ModelProfile {
+    PROFILE
+        .get()
+        .expect("embedding::configure succeeded at startup")
+}
+
+/// Cut a text to at most `max_chars` characters, at a word boundary when possible
+fn truncate_at_word(text: &str, max_chars: usize) -> &str {
+    let Some((cut, _)) = text.char_indices().nth(max_chars) else {
+        return text;
+    };
+    match text[..cut].rfind(char::is_whitespace) {
+        Some(space) if space > cut / 2 => text[..space].trim_end(),
+        _ => &text[..cut],
+    }
+}
 
 /// Global debug flag
 static DEBUG: AtomicBool = AtomicBool::new(false);
@@ -16,41 +256,81 @@ pub fn set_debug(enabled: bool) {
     DEBUG.store(enabled, Ordering::Relaxed);
 }
 
-/// Global embedding model instance (lazy-loaded, wrapped in Mutex for mutability)
-static MODEL: OnceLock<Mutex<TextEmbedding>> = OnceLock::new();
+/// In-process fastembed model
+#[cfg(feature = "semantic-local")]
+struct FastembedBackend(TextEmbedding);
+
+#[cfg(feature = "semantic-local")]
+impl FastembedBackend {
+    /// Load the model (downloads on first use, ~80MB for the default)
+    fn new(model: &EmbeddingModel) -> Result<Self> {
+        let debug = DEBUG.load(Ordering::Relaxed);
 
-/// Initialize the embedding model (downloads on first use ~80MB)
-fn init_model() -> Result<TextEmbedding> {
-    let debug = DEBUG.load(Ordering::Relaxed);
+        // Use centralized cache directory instead of current working directory
+        let cache_dir = Config::model_cache_dir()?;
+        std::fs::create_dir_all(&cache_dir).with_context(|| {
+            format!("Failed to create cache directory: {}", cache_dir.display())
+        })?;
 
-    // Use centralized cache directory instead of current working directory
-    let cache_dir = Config::model_cache_dir()?;
-    std::fs::create_dir_all(&cache_dir)
-        .with_context(|| format!("Failed to create cache directory: {}", cache_dir.display()))?;
+        let model = TextEmbedding::try_new(
+            InitOptions::new(model.clone())
+                .with_cache_dir(cache_dir)
+                .with_show_download_progress(debug),
+        )
+        .context("Failed to initialize embedding model")?;
+        Ok(Self(model))
+    }
+}
 
-    TextEmbedding::try_new(
-        InitOptions::new(EmbeddingModel::MultilingualE5Small)
-            .with_cache_dir(cache_dir)
-            .with_show_download_progress(debug),
-    )
-    .context("Failed to initialize embedding model")
+#[cfg(feature = "semantic-local")]
+impl EmbeddingBackend for FastembedBackend {
+    fn embed(&mut self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.0
+            .embed(texts, None)
+            .context("Failed to generate embeddings")
+    }
 }
 
-/// Generate embedding for a single text
-pub fn embed_text(text: &str) -> Result<Vec<f32>> {
-    let model_mutex = MODEL.get_or_init(|| {
-        Mutex::new(init_model().expect("Failed to initialize embedding model"))
-    });
+/// Global backend instance (created on first use, wrapped in Mutex for mutability)
+static BACKEND: Mutex<Option<Box<dyn EmbeddingBackend>>> = Mutex::new(None);
+
+/// Create the backend described by the profile
+fn init_backend() -> Result<Box<dyn EmbeddingBackend>> {
+    let profile = profile();
+    Ok(match &profile.source {
+        #[cfg(feature = "semantic-local")]
+        ModelSource::Fastembed(model) => Box::new(FastembedBackend::new(model)?),
+        #[cfg(feature = "semantic-http")]
+        ModelSource::Http { url, model } => Box::new(HttpBackend::new(url, model)),
+    })
+}
 
-    let mut model = model_mutex
+/// Run a batch through the backend, checking it returned one vector per text
+fn embed_batch(texts: &[String]) -> Result<Vec<Vec<f32>>> {
+    let mut backend = BACKEND
         .lock()
-        .map_err(|_| anyhow::anyhow!("Failed to lock embedding model"))?;
+        .map_err(|_| anyhow::anyhow!("Failed to lock embedding backend"))?;
+    if backend.is_none() {
+        *backend = Some(init_backend()?);
+    }
 
-    let embeddings = model
-        .embed(vec![text], None)
-        .context("Failed to generate embedding")?;
+    let embeddings = backend
+        .as_mut()
+        .expect("backend initialized above")
+        .embed(texts)?;
+    if embeddings.len() != texts.len() {
+        anyhow::bail!(
+            "Embedding backend returned {} vectors for {} texts",
+            embeddings.len(),
+            texts.len()
+        );
+    }
+    Ok(embeddings)
+}
 
-    embeddings
+/// Generate embedding for a single text
+pub fn embed_text(text: &str) -> Result<Vec<f32>> {
+    embed_batch(&[text.to_string()])?
         .into_iter()
         .next()
         .ok_or_else(|| anyhow::anyhow!("No embedding generated"))
@@ -61,17 +341,66 @@ pub fn embed_texts(texts: &[String]) -> Result<Vec<Vec<f32>>> {
     if texts.is_empty() {
         return Ok(vec![]);
     }
+    embed_batch(texts)
+}
 
-    let model_mutex = MODEL.get_or_init(|| {
-        Mutex::new(init_model().expect("Failed to initialize embedding model"))
-    });
+/// Scale a vector to unit length (cosine similarity then equals the dot product)
+pub fn normalize(vector: &[f32]) -> Vec<f32> {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return vector.to_vec();
+    }
+    vector.iter().map(|x| x / norm).collect()
+}
 
-    let mut model = model_mutex
-        .lock()
-        .map_err(|_| anyhow::anyhow!("Failed to lock embedding model"))?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    model
-        .embed(texts.to_vec(), None)
-        .context("Failed to generate embeddings")
-}
+    #[test]
+    fn truncation_stops_at_a_word_boundary() {
+        assert_eq!(truncate_at_word("short text", 50), "short text");
+        assert_eq!(truncate_at_word("payment gateway service", 12), "payment");
+        // No space in the second half: cut mid-word rather than lose most of the text
+        assert_eq!(truncate_at_word("a verylongidentifier", 10), "a verylong");
+        assert_eq!(truncate_at_word("café crème brûlée", 11), "café crème");
+    }
+
+    #[test]
+    #[cfg(feature = "semantic-http")]
+    fn midpoint_similarity_scores_half() {
+        for model in [
+            "multilingual-e5-small",
+            "bge-small-en-v1.5",
+            "nomic-embed-text",
+            "unknown",
+        ] {
+            let profile = ModelProfile::for_server(model, "http://localhost", 384);
+            let score = profile.calibrated_score(1.0 - profile.similarity_midpoint);
+            assert!((score - 50.0).abs() < 1e-3, "{model}: {score}");
+        }
+    }
 
+    #[test]
+    #[cfg(feature = "semantic-http")]
+    fn calibration_depends_on_the_model() {
+        let e5 = ModelProfile::for_server("multilingual-e5-small", "http://localhost", 384);
+        let bge = ModelProfile::for_server("bge-small-en-v1.5", "http://localhost", 384);
+        // A 0.70 cosine is unrelated for E5 but a decent match for BGE
+        assert!(e5.calibrated_score(0.30) < 5.0);
+        assert!(bge.calibrated_score(0.30) > 80.0);
+        assert!(e5.calibrated_score(0.10) > 95.0);
+    }
+
+    #[test]
+    #[cfg(feature = "semantic-http")]
+    fn server_gets_the_model_name_as_configured() {
+        let profile = ModelProfile::for_server("BAAI/BGE-M3", "http://localhost", 1024);
+        assert_eq!(profile.model_id, "baai/bge-m3");
+        match profile.source {
+            ModelSource::Http { model, .. } => assert_eq!(model, "BAAI/BGE-M3"),
+            #[allow(unreachable_patterns)]
+            _ => panic!("expected an HTTP model source"),
+        }
+    }
+}
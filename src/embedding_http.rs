@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::embedding::EmbeddingBackend;
+
+/// How long a local server gets to answer one request (first calls may load the model)
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Request/response shape, chosen from the endpoint path
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Api {
+    /// Ollama `/api/embeddings`: one `prompt` per request
+    OllamaSingle,
+    /// Ollama `/api/embed`: a batch of `input`s
+    OllamaBatch,
+    /// OpenAI-compatible `/v1/embeddings` (llama.cpp, LM Studio, vLLM, LocalAI...)
+    OpenAi,
+}
+
+impl Api {
+    fn from_url(url: &str) -> Self {
+        let path = url.trim_end_matches('/');
+        if path.ends_with("/api/embeddings") {
+            Api::OllamaSingle
+        } else if path.ends_with("/api/embed") {
+            Api::OllamaBatch
+        } else {
+            Api::OpenAi
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PromptRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+}
+
+#[derive(Serialize)]
+struct InputRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct OllamaSingleResponse {
+    embedding: Vec<f32>,
+}
+
+#[derive(Deserialize)]
+struct OllamaBatchResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiResponse {
+    data: Vec<OpenAiEmbedding>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiEmbedding {
+    embedding: Vec<f32>,
+    #[serde(default)]
+    index: usize,
+}
+
+/// Embeddings computed by a local HTTP server instead of an in-process model
+pub struct HttpBackend {
+    agent: ureq::Agent,
+    url: String,
+    model: String,
+    api: Api,
+}
+
+impl HttpBackend {
+    pub fn new(url: &str, model: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+            url: url.to_string(),
+            model: model.to_string(),
+            api: Api::from_url(url),
+        }
+    }
+
+    fn post<T: for<'de> Deserialize<'de>>(&self, body: impl Serialize) -> Result<T> {
+        let response = self
+            .agent
+            .post(&self.url)
+            .send_json(body)
+            .map_err(|e| match e {
+                ureq::Error::Status(code, response) => anyhow::anyhow!(
+                    "Embedding server at {} answered {}: {}",
+                    self.url,
+                    code,
+                    response.into_string().unwrap_or_default().trim()
+                ),
+                ureq::Error::Transport(e) => anyhow::anyhow!("Embedding server unreachable: {}", e),
+            })?;
+
+        response
+            .into_json()
+            .with_context(|| format!("Unexpected response from embedding server at {}", self.url))
+    }
+}
+
+impl EmbeddingBackend for HttpBackend {
+    fn embed(&mut self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        match self.api {
+            Api::OllamaSingle => texts
+                .iter()
+                .map(|text| {
+                    let response: OllamaSingleResponse = self.post(PromptRequest {
+                        model: &self.model,
+                        prompt: text,
+                    })?;
+                    Ok(response.embedding)
+                })
+                .collect(),
+            Api::OllamaBatch => {
+                let response: OllamaBatchResponse = self.post(InputRequest {
+                    model: &self.model,
+                    input: texts,
+                })?;
+                Ok(response.embeddings)
+            }
+            Api::OpenAi => {
+                let mut response: OpenAiResponse = self.post(InputRequest {
+                    model: &self.model,
+                    input: texts,
+                })?;
+                response.data.sort_by_key(|d| d.index);
+                Ok(response.data.into_iter().map(|d| d.embedding).collect())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_follows_the_endpoint_path() {
+        assert_eq!(
+            Api::from_url("http://localhost:11434/api/embeddings"),
+            Api::OllamaSingle
+        );
+        assert_eq!(
+            Api::from_url("http://localhost:11434/api/embed/"),
+            Api::OllamaBatch
+        );
+        assert_eq!(
+            Api::from_url("http://localhost:8080/v1/embeddings"),
+            Api::OpenAi
+        );
+    }
+}
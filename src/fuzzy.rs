@@ -0,0 +1,371 @@
+use std::path::Path;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Points for every matched character
+const MATCH_SCORE: i32 = 16;
+
+/// Bonus when a character matches at the start of a word
+/// (after a separator, on a camelCase hump, or at a letter/digit transition)
+const BOUNDARY_BONUS: i32 = 10;
+
+/// Bonus when a character directly follows the previous match
+const CONSECUTIVE_BONUS: i32 = 6;
+
+/// Extra bonus when the first query character matches the first target character
+const FIRST_CHAR_BONUS: i32 = 8;
+
+/// Penalty when the first query character lands in the middle of a word
+const MID_WORD_START_PENALTY: i32 = 8;
+
+/// Penalty per skipped target character between two matches
+const GAP_PENALTY: i32 = 2;
+
+/// Path components get a lower weight than the project name itself
+const PATH_COMPONENT_WEIGHT: f32 = 0.7;
+
+/// Number of parent directories considered when scoring path components
+const PATH_COMPONENT_DEPTH: usize = 3;
+
+/// Number of parent directories a multi-part query (`clientA/api`) can refer to
+const SEGMENT_PATH_DEPTH: usize = 4;
+
+/// Minimum score for each part of a multi-part query to count as matching
+const SEGMENT_MIN_SCORE: f32 = 60.0;
+
+/// Share of the name in a multi-part match (the rest goes to the directories)
+const SEGMENT_NAME_WEIGHT: f32 = 0.6;
+
+/// Fold a string for matching: strip diacritics and lowercase (é → e, Ü → u)
+pub fn fold(s: &str) -> String {
+    s.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn is_separator(c: char) -> bool {
+    !c.is_alphanumeric()
+}
+
+/// Fold a target string, keeping track of which folded characters start a word
+fn fold_with_boundaries(s: &str) -> (Vec<char>, Vec<bool>) {
+    let mut chars = Vec::with_capacity(s.len());
+    let mut boundaries = Vec::with_capacity(s.len());
+    let mut prev: Option<char> = None;
+
+    for c in s.chars() {
+        let is_boundary = match prev {
+            None => true,
+            Some(p) => {
+                is_separator(p)
+                    || (p.is_lowercase() && c.is_uppercase())
+                    || (p.is_alphabetic() && c.is_numeric())
+                    || (p.is_numeric() && c.is_alphabetic())
+            }
+        };
+        prev = Some(c);
+
+        if is_separator(c) {
+            continue;
+        }
+
+        let mut first = true;
+        for folded in fold(&c.to_string()).chars() {
+            chars.push(folded);
+            boundaries.push(is_boundary && first);
+            first = false;
+        }
+    }
+
+    (chars, boundaries)
+}
+
+/// Score how well `query` fuzzily matches `target` (0-100, 0 = no match)
+///
+/// The query must appear as a subsequence of the target (separators ignored).
+/// Matches at word starts and runs of consecutive characters score higher, so
+/// `gcp` matches `goto-cli-project` and `ltc` matches `LruTtlCache`.
+pub fn score(query: &str, target: &str) -> f32 {
+    let query: Vec<char> = fold(query).chars().filter(|c| !is_separator(*c)).collect();
+    let (target, boundaries) = fold_with_boundaries(target);
+
+    let m = query.len();
+    let n = target.len();
+    if m == 0 || n == 0 || m > n {
+        return 0.0;
+    }
+
+    if query == target {
+        return 100.0;
+    }
+
+    // dp[j] = best score for query[..=i] with query[i] matched at target[j]
+    let mut prev_row: Vec<Option<i32>> = vec![None; n];
+    let mut row: Vec<Option<i32>> = vec![None; n];
+
+    for (i, qc) in query.iter().enumerate() {
+        // Best of prev_row[k] + GAP_PENALTY * k over k < j - 1 (linear gap trick)
+        let mut best_gapped: Option<i32> = None;
+
+        for j in 0..n {
+            row[j] = None;
+
+            if j >= 2 && i > 0 {
+                if let Some(s) = prev_row[j - 2] {
+                    let candidate = s + GAP_PENALTY * (j as i32 - 2);
+                    best_gapped = Some(best_gapped.map_or(candidate, |b| b.max(candidate)));
+                }
+            }
+
+            if target[j] != *qc {
+                continue;
+            }
+
+            let mut bonus = MATCH_SCORE;
+            if boundaries[j] {
+                bonus += BOUNDARY_BONUS;
+            }
+
+            if i == 0 {
+                if j == 0 {
+                    bonus += FIRST_CHAR_BONUS;
+                } else if !boundaries[j] {
+                    bonus -= MID_WORD_START_PENALTY;
+                }
+                // Small penalty for starting late in the target
+                row[j] = Some(bonus - (j as i32).min(10));
+                continue;
+            }
+
+            let consecutive = if j >= 1 {
+                prev_row[j - 1].map(|s| s + CONSECUTIVE_BONUS)
+            } else {
+                None
+            };
+            let gapped = best_gapped.map(|b| b - GAP_PENALTY * (j as i32 - 1));
+
+            row[j] = match (consecutive, gapped) {
+                (Some(c), Some(g)) => Some(c.max(g) + bonus),
+                (Some(c), None) => Some(c + bonus),
+                (None, Some(g)) => Some(g + bonus),
+                (None, None) => None,
+            };
+        }
+
+        std::mem::swap(&mut prev_row, &mut row);
+    }
+
+    let best = match prev_row.iter().flatten().max() {
+        Some(b) => *b,
+        None => return 0.0,
+    };
+
+    // A perfect match hits a word start or continues a run on every character
+    let ideal = m as i32 * (MATCH_SCORE + BOUNDARY_BONUS) + FIRST_CHAR_BONUS;
+    let quality = (best.max(0) as f32 / ideal as f32).min(1.0);
+
+    // Prefer targets that the query covers more completely
+    let coverage = m as f32 / n as f32;
+
+    (quality * (0.8 + 0.2 * coverage) * 100.0).min(99.0)
+}
+
+/// Score a project against the query using its name and nearest parent directories
+pub fn score_project(query: &str, name: &str, path: &Path) -> f32 {
+    let name_score = score(query, name);
+
+    let parent_score = path
+        .parent()
+        .into_iter()
+        .flat_map(|p| p.components().rev().take(PATH_COMPONENT_DEPTH))
+        .map(|c| score(query, &c.as_os_str().to_string_lossy()) * PATH_COMPONENT_WEIGHT)
+        .fold(0.0_f32, f32::max);
+
+    let segment_score = score_path_segments(query, name, path).unwrap_or(0.0);
+
+    name_score.max(parent_score).max(segment_score)
+}
+
+/// Score a multi-part query (`work/api`, `clientA api`) against a project path
+///
+/// One part matches the project name and every other part must match one of
+/// its parent directories. With slashes the parts are read as a path: the
+/// last one is the name and the others appear in the same order in the
+/// path. With spaces any word can be the name. Returns None when the query
+/// has a single part or some part matches nothing.
+fn score_path_segments(query: &str, name: &str, path: &Path) -> Option<f32> {
+    let ordered = query.contains('/');
+    let parts: Vec<&str> = query
+        .split(|c: char| c == '/' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if parts.len() < 2 {
+        return None;
+    }
+
+    // Nearest parent first
+    let ancestors: Vec<String> = path
+        .parent()
+        .into_iter()
+        .flat_map(|p| p.components().rev().take(SEGMENT_PATH_DEPTH))
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+
+    let name_positions: Vec<usize> = if ordered {
+        vec![parts.len() - 1]
+    } else {
+        (0..parts.len()).collect()
+    };
+
+    name_positions
+        .into_iter()
+        .filter_map(|i| {
+            let name_score = score(parts[i], name);
+            if name_score < SEGMENT_MIN_SCORE {
+                return None;
+            }
+
+            let dir_scores = if ordered {
+                match_in_path_order(&parts[..i], &ancestors)?
+            } else {
+                parts
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, part)| best_directory_score(part, &ancestors))
+                    .collect::<Option<Vec<f32>>>()?
+            };
+
+            let dir_mean = dir_scores.iter().sum::<f32>() / dir_scores.len() as f32;
+            Some(
+                (SEGMENT_NAME_WEIGHT * name_score + (1.0 - SEGMENT_NAME_WEIGHT) * dir_mean)
+                    .min(99.0),
+            )
+        })
+        .reduce(f32::max)
+}
+
+/// Best score of a query part against any of the directories
+fn best_directory_score(part: &str, ancestors: &[String]) -> Option<f32> {
+    ancestors
+        .iter()
+        .map(|dir| score(part, dir))
+        .filter(|s| *s >= SEGMENT_MIN_SCORE)
+        .reduce(f32::max)
+}
+
+/// Match `work/clientA` (read left to right) against ancestors listed nearest
+/// first: the last part must be matched below the one before it
+fn match_in_path_order(parts: &[&str], ancestors: &[String]) -> Option<Vec<f32>> {
+    let mut scores = Vec::with_capacity(parts.len());
+    let mut next = 0;
+
+    for part in parts.iter().rev() {
+        let (index, part_score) = ancestors
+            .iter()
+            .enumerate()
+            .skip(next)
+            .map(|(i, dir)| (i, score(part, dir)))
+            .find(|(_, s)| *s >= SEGMENT_MIN_SCORE)?;
+        scores.push(part_score);
+        next = index + 1;
+    }
+
+    Some(scores)
+}
+
+/// Similarity from the Levenshtein edit distance (1.0 = identical, 0.0 = nothing in common)
+///
+/// Unlike `score`, typos count: `kafak-connect` is close to `kafka-connect`.
+pub fn edit_similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = fold(a).chars().collect();
+    let b: Vec<char> = fold(b).chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    // Single-row dynamic programming
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    1.0 - row[b.len()] as f32 / longest as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fold_strips_diacritics_and_case() {
+        assert_eq!(fold("Café-Über"), "cafe-uber");
+    }
+
+    #[test]
+    fn exact_match_scores_full() {
+        assert_eq!(score("billing-api", "billing-api"), 100.0);
+        assert_eq!(score("billingapi", "Billing_API"), 100.0);
+    }
+
+    #[test]
+    fn non_subsequence_does_not_match() {
+        assert_eq!(score("xyz", "billing-api"), 0.0);
+        assert_eq!(score("", "billing-api"), 0.0);
+        assert_eq!(score("billing-api-v2", "billing-api"), 0.0);
+    }
+
+    #[test]
+    fn word_starts_beat_scattered_letters() {
+        let initials = score("gcp", "goto-cli-project");
+        assert!(initials > 0.0);
+        assert!(initials > score("gcp", "spacecgp-ogcp"));
+        assert!(score("ltc", "LruTtlCache") > score("ltc", "altcoin"));
+    }
+
+    #[test]
+    fn prefix_beats_substring() {
+        assert!(score("bill", "billing-api") > score("bill", "prebilling"));
+    }
+
+    #[test]
+    fn project_scores_parent_directories_lower() {
+        let path = Path::new("/home/me/code/kafka/connect");
+        let by_name = score_project("connect", "connect", path);
+        let by_parent = score_project("kafka", "connect", path);
+        assert_eq!(by_name, 100.0);
+        assert!(by_parent > 0.0 && by_parent < by_name);
+    }
+
+    #[test]
+    fn edit_similarity_counts_typos() {
+        assert_eq!(edit_similarity("kafka", "kafka"), 1.0);
+        assert_eq!(edit_similarity("", ""), 1.0);
+        assert_eq!(edit_similarity("abc", ""), 0.0);
+        assert_eq!(edit_similarity("Kafka", "kafka"), 1.0);
+        // One substitution out of five characters, a transposition is two
+        assert!((edit_similarity("kafxa", "kafka") - 0.8).abs() < 1e-6);
+        assert!((edit_similarity("kafak", "kafka") - 0.6).abs() < 1e-6);
+        assert!(edit_similarity("kafak-connect", "kafka-connect") > 0.8);
+        assert_eq!(edit_similarity("abc", "xyz"), 0.0);
+    }
+
+    #[test]
+    fn multi_part_queries_match_parent_directories() {
+        let path = Path::new("/home/me/work/clientA/api");
+        assert!(score_path_segments("clientA/api", "api", path).unwrap() > SEGMENT_MIN_SCORE);
+        assert!(score_path_segments("api clientA", "api", path).is_some());
+        // With slashes the parts are read in path order
+        assert_eq!(score_path_segments("api/clientA", "api", path), None);
+        assert_eq!(score_path_segments("clientB/api", "api", path), None);
+        assert_eq!(score_path_segments("api", "api", path), None);
+    }
+}
@@ -0,0 +1,82 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Get git branch and dirty status for a project
+pub fn status(path: &Path) -> Option<(String, bool)> {
+    // Get current branch
+    let branch_output = Command::new("git")
+        .args([
+            "-C",
+            &path.to_string_lossy(),
+            "rev-parse",
+            "--abbrev-ref",
+            "HEAD",
+        ])
+        .output()
+        .ok()?;
+
+    if !branch_output.status.success() {
+        return None;
+    }
+
+    let branch = String::from_utf8_lossy(&branch_output.stdout)
+        .trim()
+        .to_string();
+
+    // Check if dirty (has uncommitted changes)
+    let status_output = Command::new("git")
+        .args(["-C", &path.to_string_lossy(), "status", "--porcelain"])
+        .output()
+        .ok()?;
+
+    let is_dirty = !status_output.stdout.is_empty();
+
+    Some((branch, is_dirty))
+}
+
+/// Git directory of a project (worktrees and submodules have a `.git` file
+/// pointing to the real directory)
+fn git_dir(path: &Path) -> Option<PathBuf> {
+    let dot_git = path.join(".git");
+    if dot_git.is_file() {
+        let content = std::fs::read_to_string(&dot_git).ok()?;
+        Some(path.join(content.strip_prefix("gitdir:")?.trim()))
+    } else {
+        dot_git.is_dir().then_some(dot_git)
+    }
+}
+
+/// Index file of a project. Git rewrites it on add, commit, checkout and
+/// every `git status` that sees a modified file, so its mtime follows
+/// working-tree edits without walking the tree.
+pub fn index_file(path: &Path) -> Option<PathBuf> {
+    Some(git_dir(path)?.join("index"))
+}
+
+/// Commit checked out in a project, read from .git without spawning git
+/// (cheap enough to call for every project during `goto update`)
+pub fn head_commit(path: &Path) -> Option<String> {
+    let git_dir = git_dir(path)?;
+
+    let head = std::fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let Some(reference) = head.trim().strip_prefix("ref:") else {
+        // Detached HEAD holds the commit itself
+        return Some(head.trim().to_string());
+    };
+    let reference = reference.trim();
+
+    // Branch refs live in the main repository for worktrees
+    let common_dir = std::fs::read_to_string(git_dir.join("commondir"))
+        .map(|dir| git_dir.join(dir.trim()))
+        .unwrap_or(git_dir);
+
+    if let Ok(commit) = std::fs::read_to_string(common_dir.join(reference)) {
+        return Some(commit.trim().to_string());
+    }
+
+    let packed = std::fs::read_to_string(common_dir.join("packed-refs")).ok()?;
+    packed.lines().find_map(|line| {
+        let (commit, name) = line.split_once(' ')?;
+        (name == reference).then(|| commit.to_string())
+    })
+}
@@ -0,0 +1,307 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::db::Database;
+use crate::fuzzy;
+use crate::lexical;
+use crate::ranking::{NameBoost, ScoredProject};
+
+/// Maximum relevance points for a project repeatedly chosen for the same query
+const LEARNED_QUERY_BOOST: f32 = 25.0;
+
+/// Pseudo-count damping the query preference until a choice is repeated
+/// (1 selection → 1/3 of the boost, 3 → 3/5, 8 → 4/5)
+const PREFERENCE_PRIOR: f32 = 2.0;
+
+/// Age in days at which a past selection counts half as much
+const SELECTION_HALF_LIFE_DAYS: f32 = 60.0;
+
+/// A similar query this soon after a jump, from inside the project it landed
+/// in and going elsewhere, means the jump went to the wrong place
+const REQUERY_WINDOW_SECS: i64 = 30;
+
+/// Edit similarity from which two queries without a common word count as
+/// the same search retyped (`kafak` then `kafka`)
+const REQUERY_MIN_SIMILARITY: f32 = 0.6;
+
+/// index_info key holding the last jump as `timestamp<TAB>path<TAB>query`
+const LAST_JUMP_KEY: &str = "last_jump";
+
+/// Relative step applied to a boost weight after each corrective selection
+const WEIGHT_LEARNING_RATE: f32 = 0.1;
+
+/// Bounds for learned boost weights (multipliers of the base constants)
+const MIN_WEIGHT: f32 = 0.5;
+const MAX_WEIGHT: f32 = 2.0;
+
+/// Number of top candidates stored with each selection
+const RECORDED_CANDIDATES: usize = 5;
+
+/// Multipliers applied to the name/metadata boost constants
+#[derive(Debug, Clone, Copy)]
+pub struct BoostWeights {
+    pub exact_name: f32,
+    pub substring: f32,
+    pub metadata: f32,
+}
+
+impl Default for BoostWeights {
+    fn default() -> Self {
+        Self {
+            exact_name: 1.0,
+            substring: 1.0,
+            metadata: 1.0,
+        }
+    }
+}
+
+impl BoostWeights {
+    fn weight_mut(&mut self, boost: NameBoost) -> Option<&mut f32> {
+        match boost {
+            NameBoost::ExactName => Some(&mut self.exact_name),
+            NameBoost::Substring => Some(&mut self.substring),
+            NameBoost::Metadata => Some(&mut self.metadata),
+            NameBoost::None => None,
+        }
+    }
+
+    /// (database key, value) pairs
+    pub fn entries(&self) -> [(&'static str, f32); 3] {
+        [
+            ("exact_name", self.exact_name),
+            ("substring", self.substring),
+            ("metadata", self.metadata),
+        ]
+    }
+}
+
+/// Normalize a query so trivially different spellings share what was learned
+pub fn query_key(query: &str) -> String {
+    fuzzy::fold(query)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Load learned boost weights (defaults for anything not learned yet)
+pub fn load_weights(db: &Database) -> Result<BoostWeights> {
+    let mut weights = BoostWeights::default();
+    for (name, value) in db.get_ranking_weights()? {
+        match name.as_str() {
+            "exact_name" => weights.exact_name = value,
+            "substring" => weights.substring = value,
+            "metadata" => weights.metadata = value,
+            _ => {}
+        }
+    }
+    Ok(weights)
+}
+
+/// Weight of a selection made at `when`, halving every SELECTION_HALF_LIFE_DAYS
+fn decay(when: DateTime<Utc>, now: DateTime<Utc>) -> f32 {
+    let age_days = (now - when).num_seconds().max(0) as f32 / 86_400.0;
+    0.5_f32.powf(age_days / SELECTION_HALF_LIFE_DAYS)
+}
+
+/// Relevance points per project path, from past selections for this query
+///
+/// Selections add to a project's preference and rejections subtract from
+/// it, both fading with age, so the result lies within ±LEARNED_QUERY_BOOST.
+pub fn query_preferences(db: &Database, query: &str) -> Result<HashMap<PathBuf, f32>> {
+    let key = query_key(query);
+    if key.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let now = Utc::now();
+    let mut balance: HashMap<PathBuf, f32> = HashMap::new();
+    let mut total = 0.0;
+    for (path, when, rejected) in db.get_selections(&key)? {
+        let weight = decay(when, now);
+        *balance.entry(path).or_insert(0.0) += if rejected { -weight } else { weight };
+        total += weight;
+    }
+
+    Ok(balance
+        .into_iter()
+        .map(|(path, net)| (path, LEARNED_QUERY_BOOST * net / (total + PREFERENCE_PRIOR)))
+        .collect())
+}
+
+/// Record which project was explicitly chosen for a query among the candidates shown
+///
+/// Only picks made by the user count (the ambiguity prompt, or a pick after
+/// `goto -a`): recording automatic jumps would reinforce whatever ranked
+/// first. When the choice is not the top-ranked candidate, the boost rule that
+/// favoured the top candidate is weakened and the one matching the chosen
+/// project is strengthened.
+pub fn record_selection(
+    db: &Database,
+    query: &str,
+    shown: &[ScoredProject],
+    chosen: &Path,
+) -> Result<()> {
+    let key = query_key(query);
+    if key.is_empty() {
+        return Ok(());
+    }
+
+    let shown_paths: Vec<&Path> = shown
+        .iter()
+        .take(RECORDED_CANDIDATES)
+        .map(|c| c.project.path.as_path())
+        .collect();
+    db.insert_selection(&key, &shown_paths, chosen)?;
+
+    let (Some(top), Some(picked)) = (
+        shown.first(),
+        shown.iter().find(|c| c.project.path == chosen),
+    ) else {
+        return Ok(());
+    };
+    if top.project.path == picked.project.path || top.boost == picked.boost {
+        return Ok(());
+    }
+
+    let mut weights = load_weights(db)?;
+    if let Some(w) = weights.weight_mut(top.boost) {
+        *w = (*w * (1.0 - WEIGHT_LEARNING_RATE)).clamp(MIN_WEIGHT, MAX_WEIGHT);
+    }
+    if let Some(w) = weights.weight_mut(picked.boost) {
+        *w = (*w * (1.0 + WEIGHT_LEARNING_RATE)).clamp(MIN_WEIGHT, MAX_WEIGHT);
+    }
+    db.set_ranking_weights(&weights.entries())
+}
+
+/// Remember a jump, so that a query right after it can mark it as a miss
+pub fn remember_jump(db: &Database, query: &str, path: &Path, cwd: Option<&Path>) -> Result<()> {
+    let key = query_key(query);
+
+    // The previous jump missed when this one corrects it
+    if let Some(last) = db
+        .get_info(LAST_JUMP_KEY)?
+        .as_deref()
+        .and_then(LastJump::parse)
+    {
+        if last.corrected_by(&key, path, cwd, Utc::now()) {
+            db.insert_rejection(&last.query, &last.path)?;
+        }
+    }
+
+    if key.is_empty() {
+        return db.remove_info(LAST_JUMP_KEY);
+    }
+    db.set_info(
+        LAST_JUMP_KEY,
+        &format!("{}\t{}\t{}", Utc::now().to_rfc3339(), path.display(), key),
+    )
+}
+
+/// A jump remembered by `remember_jump`
+#[derive(Debug)]
+struct LastJump {
+    when: DateTime<Utc>,
+    path: PathBuf,
+    /// Folded query (see `query_key`)
+    query: String,
+}
+
+impl LastJump {
+    fn parse(stored: &str) -> Option<Self> {
+        let mut fields = stored.splitn(3, '\t');
+        let when = DateTime::parse_from_rfc3339(fields.next()?)
+            .ok()?
+            .with_timezone(&Utc);
+        let path = PathBuf::from(fields.next()?);
+        let query = fields.next()?.to_string();
+        Some(Self { when, path, query })
+    }
+
+    /// Whether jumping to `path` for `query` shows this jump went to the wrong
+    /// place: it follows right after, from inside the project this jump landed
+    /// in, looks for something similar and lands elsewhere. Moving on to an
+    /// unrelated project is not a correction.
+    fn corrected_by(
+        &self,
+        query: &str,
+        path: &Path,
+        cwd: Option<&Path>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let recent = (now - self.when).num_seconds() <= REQUERY_WINDOW_SECS;
+        let from_landing = cwd.is_some_and(|c| c.starts_with(&self.path));
+        recent && from_landing && path != self.path && queries_similar(&self.query, query)
+    }
+}
+
+/// Whether two queries are the same search retyped: a word in common, or a
+/// small edit distance (typo fixes)
+fn queries_similar(a: &str, b: &str) -> bool {
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    let words = lexical::tokenize(a);
+    lexical::tokenize(b).iter().any(|word| words.contains(word))
+        || fuzzy::edit_similarity(a, b) >= REQUERY_MIN_SIMILARITY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn query_key_folds_case_accents_and_spacing() {
+        assert_eq!(query_key("  Café   API "), "cafe api");
+    }
+
+    #[test]
+    fn selections_fade_with_age() {
+        let now = Utc::now();
+        assert!((decay(now, now) - 1.0).abs() < 1e-6);
+        let half = decay(now - Duration::days(SELECTION_HALF_LIFE_DAYS as i64), now);
+        assert!((half - 0.5).abs() < 1e-3);
+        assert!(decay(now - Duration::days(365), now) < 0.05);
+        // Clock skew never boosts a selection
+        assert!((decay(now + Duration::days(1), now) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn requery_corrects_a_jump_only_when_similar_and_elsewhere() {
+        let now = Utc::now();
+        let last = LastJump::parse(&format!(
+            "{}\tcode/kafka-ui\tkafka",
+            (now - Duration::seconds(10)).to_rfc3339()
+        ))
+        .unwrap();
+        let inside = Some(Path::new("code/kafka-ui/src"));
+
+        // Same search retyped, landing on another project
+        assert!(last.corrected_by(
+            "kafka connect",
+            Path::new("code/kafka-connect"),
+            inside,
+            now
+        ));
+        assert!(last.corrected_by("kafak", Path::new("code/kafka-connect"), inside, now));
+        // Moving on to something unrelated, or landing on the same project again
+        assert!(!last.corrected_by("billing", Path::new("code/billing-api"), inside, now));
+        assert!(!last.corrected_by("kafka", Path::new("code/kafka-ui"), inside, now));
+        // From elsewhere, or too late
+        assert!(!last.corrected_by(
+            "kafka connect",
+            Path::new("code/kafka-connect"),
+            Some(Path::new("code")),
+            now
+        ));
+        let later = now + Duration::seconds(REQUERY_WINDOW_SECS);
+        assert!(!last.corrected_by(
+            "kafka connect",
+            Path::new("code/kafka-connect"),
+            inside,
+            later
+        ));
+    }
+}
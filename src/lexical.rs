@@ -0,0 +1,349 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use crate::fuzzy;
+
+/// Smoothing constant for reciprocal rank fusion (standard value from the RRF paper)
+const RRF_K: f32 = 60.0;
+
+/// Relevance points for a query term found in each full-text column
+/// (name, description, keywords, readme, types, tech), on the same 0-100
+/// scale as calibrated semantic similarity
+const COLUMN_SCORES: [f32; 6] = [75.0, 60.0, 65.0, 45.0, 65.0, 55.0];
+
+/// Split an identifier on camelCase humps and letter/digit transitions
+/// (`LruCache` → [Lru, Cache], `HTTPServer2` → [HTTP, Server, 2])
+fn split_camel_case(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let mut parts = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if i > 0 && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next = chars.get(i + 1).copied();
+            let hump = prev.is_lowercase() && c.is_uppercase();
+            let acronym_end = prev.is_uppercase()
+                && c.is_uppercase()
+                && next.map(|n| n.is_lowercase()).unwrap_or(false);
+            let digit_change = prev.is_numeric() != c.is_numeric();
+            if hump || acronym_end || digit_change {
+                parts.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Split text into identifier-aware tokens
+///
+/// Every alphanumeric word is kept as-is and, when it is a compound
+/// (camelCase, snake_case, kebab-case), its parts are added as extra tokens,
+/// so both `LruCache` and `lru cache` find the same project.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+
+    for word in text.split(|c: char| c.is_whitespace() || matches!(c, ',' | '|' | '/' | '(' | ')'))
+    {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+        if word.is_empty() {
+            continue;
+        }
+
+        let pieces: Vec<&str> = word
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|p| !p.is_empty())
+            .collect();
+        let joined: String = pieces.concat();
+        tokens.push(fuzzy::fold(&joined));
+
+        let mut parts: Vec<String> = Vec::new();
+        for piece in &pieces {
+            parts.extend(split_camel_case(piece));
+        }
+        if parts.len() > 1 {
+            tokens.extend(parts.iter().map(|p| fuzzy::fold(p)));
+        }
+    }
+
+    tokens
+}
+
+/// Expand text for the FTS5 index (original words followed by compound parts)
+pub fn index_text(text: &str) -> String {
+    tokenize(text).join(" ")
+}
+
+/// Build an FTS5 MATCH expression from a free-text query
+///
+/// Each token is quoted to keep FTS5 syntax characters inert. Tokens of
+/// 3+ characters match as prefixes. Returns None when nothing is searchable.
+pub fn match_expression(query: &str) -> Option<String> {
+    let mut seen = std::collections::HashSet::new();
+    let terms: Vec<String> = tokenize(query)
+        .into_iter()
+        .filter(|t| seen.insert(t.clone()))
+        .map(|t| {
+            let quoted = format!("\"{}\"", t.replace('"', ""));
+            if t.chars().count() >= 3 {
+                format!("{}*", quoted)
+            } else {
+                quoted
+            }
+        })
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" OR "))
+    }
+}
+
+/// Whether an indexed token matches a query term the way `match_expression`
+/// does (terms of 3+ characters match as prefixes)
+fn term_matches(term: &str, token: &str) -> bool {
+    if term.chars().count() >= 3 {
+        token.starts_with(term)
+    } else {
+        token == term
+    }
+}
+
+/// Relevance (0-100) of a full-text hit, independent of its rank
+///
+/// Each query term earns the points of the best column it appears in, and
+/// the score is their average: a name containing every term scores high,
+/// a README mentioning one term out of three scores low.
+pub fn hit_score(query: &str, columns: &[String; 6]) -> f32 {
+    let mut seen = std::collections::HashSet::new();
+    let terms: Vec<String> = tokenize(query)
+        .into_iter()
+        .filter(|t| seen.insert(t.clone()))
+        .collect();
+    if terms.is_empty() {
+        return 0.0;
+    }
+
+    let total: f32 = terms
+        .iter()
+        .map(|term| {
+            columns
+                .iter()
+                .zip(COLUMN_SCORES)
+                .filter(|(column, _)| {
+                    column
+                        .split_whitespace()
+                        .any(|token| term_matches(term, token))
+                })
+                .map(|(_, points)| points)
+                .fold(0.0, f32::max)
+        })
+        .sum();
+    total / terms.len() as f32
+}
+
+/// Number of distinct query terms found in a text (same matching as `hit_score`)
+pub fn matched_terms(query: &str, text: &str) -> usize {
+    let tokens = tokenize(text);
+    let mut seen = std::collections::HashSet::new();
+    tokenize(query)
+        .into_iter()
+        .filter(|t| seen.insert(t.clone()))
+        .filter(|term| tokens.iter().any(|token| term_matches(term, token)))
+        .count()
+}
+
+/// Merge several ranked lists with reciprocal rank fusion
+///
+/// Each list contributes 1 / (k + rank) for every item it contains; items
+/// found near the top of several lists end up first.
+pub fn reciprocal_rank_fusion<T: Eq + Hash + Clone>(lists: &[Vec<T>]) -> HashMap<T, f32> {
+    let mut fused: HashMap<T, f32> = HashMap::new();
+    for list in lists {
+        for (rank, item) in list.iter().enumerate() {
+            *fused.entry(item.clone()).or_insert(0.0) += 1.0 / (RRF_K + rank as f32 + 1.0);
+        }
+    }
+    fused
+}
+
+/// A query with the alternatives from the user's synonym dictionary
+#[derive(Debug, Clone, Default)]
+pub struct ExpandedQuery {
+    /// Query as typed
+    pub original: String,
+    /// Folded query text
+    pub text: String,
+    /// Alternatives for the query as a whole (e.g. a codename mapped to a repo name)
+    pub whole: Vec<String>,
+    /// For each query word: the word itself followed by its synonyms (all folded)
+    pub words: Vec<Vec<String>>,
+}
+
+impl ExpandedQuery {
+    /// Look up the whole query and each of its words in the synonym dictionary
+    /// (keys are compared folded, so `CP` and `cp` are the same entry)
+    pub fn new(query: &str, synonyms: &BTreeMap<String, Vec<String>>) -> Self {
+        let text = fuzzy::fold(query)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let lookup = |term: &str| -> Vec<String> {
+            synonyms
+                .iter()
+                .filter(|(key, _)| {
+                    fuzzy::fold(key)
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                        == term
+                })
+                .flat_map(|(_, expansions)| expansions.iter().map(|e| fuzzy::fold(e)))
+                .collect()
+        };
+
+        let whole = if text.contains(' ') {
+            lookup(&text)
+        } else {
+            Vec::new()
+        };
+        let words = text
+            .split_whitespace()
+            .map(|word| {
+                let mut alternatives = vec![word.to_string()];
+                alternatives.extend(lookup(word));
+                alternatives
+            })
+            .collect();
+
+        Self {
+            original: query.to_string(),
+            text,
+            whole,
+            words,
+        }
+    }
+
+    /// All alternatives for the query as a whole, including single-word queries
+    pub fn whole_alternatives(&self) -> Vec<&str> {
+        let mut alternatives: Vec<&str> = self.whole.iter().map(String::as_str).collect();
+        if let [single] = self.words.as_slice() {
+            alternatives.extend(single.iter().skip(1).map(String::as_str));
+        }
+        alternatives
+    }
+
+    /// Query as typed followed by the synonyms found, for embedding and full-text search
+    pub fn search_text(&self) -> String {
+        let mut parts = vec![self.original.clone()];
+        for expansion in self
+            .whole
+            .iter()
+            .chain(self.words.iter().flat_map(|w| w.iter().skip(1)))
+        {
+            if !parts.contains(expansion) {
+                parts.push(expansion.clone());
+            }
+        }
+        parts.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_splits_compounds() {
+        assert_eq!(tokenize("LruCache"), vec!["lrucache", "lru", "cache"]);
+        assert_eq!(
+            tokenize("billing_api"),
+            vec!["billingapi", "billing", "api"]
+        );
+        assert_eq!(
+            tokenize("HTTPServer2"),
+            vec!["httpserver2", "http", "server", "2"]
+        );
+        assert_eq!(tokenize("plain words"), vec!["plain", "words"]);
+    }
+
+    #[test]
+    fn match_expression_quotes_and_prefixes() {
+        assert_eq!(
+            match_expression("kafka go").as_deref(),
+            Some("\"kafka\"* OR \"go\"")
+        );
+        assert_eq!(
+            match_expression("say \"hi\" hi").as_deref(),
+            Some("\"say\"* OR \"hi\"")
+        );
+        assert_eq!(match_expression("  --  "), None);
+    }
+
+    fn columns(name: &str, readme: &str) -> [String; 6] {
+        [
+            index_text(name),
+            String::new(),
+            String::new(),
+            index_text(readme),
+            String::new(),
+            String::new(),
+        ]
+    }
+
+    #[test]
+    fn hit_score_depends_on_column_and_coverage() {
+        let in_name = hit_score("billing api", &columns("billing-api", ""));
+        let in_readme = hit_score("billing api", &columns("web", "Talks to the billing API"));
+        let partial = hit_score("billing api", &columns("web", "billing dashboard"));
+        assert_eq!(in_name, COLUMN_SCORES[0]);
+        assert_eq!(in_readme, COLUMN_SCORES[3]);
+        assert_eq!(partial, COLUMN_SCORES[3] / 2.0);
+        assert_eq!(hit_score("kafka", &columns("web", "")), 0.0);
+    }
+
+    #[test]
+    fn hit_score_prefix_matches_long_terms_only() {
+        assert_eq!(hit_score("bill", &columns("billing", "")), COLUMN_SCORES[0]);
+        assert_eq!(hit_score("go", &columns("golang", "")), 0.0);
+        assert_eq!(hit_score("go", &columns("go-tools", "")), COLUMN_SCORES[0]);
+    }
+
+    #[test]
+    fn rrf_favours_items_high_in_several_lists() {
+        let fused = reciprocal_rank_fusion(&[vec!["a", "b", "c"], vec!["b", "a"], vec!["b"]]);
+        assert!(fused["b"] > fused["a"]);
+        assert!(fused["a"] > fused["c"]);
+        assert!((fused["c"] - 1.0 / (RRF_K + 3.0)).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn expanded_query_looks_up_whole_query_and_words() {
+        let synonyms = BTreeMap::from([
+            ("CP".to_string(), vec!["control-plane".to_string()]),
+            ("billing svc".to_string(), vec!["Invoicer".to_string()]),
+        ]);
+
+        let single = ExpandedQuery::new("cp", &synonyms);
+        assert_eq!(single.words, [vec!["cp", "control-plane"]]);
+        assert_eq!(single.whole_alternatives(), ["control-plane"]);
+        assert_eq!(single.search_text(), "cp control-plane");
+
+        let phrase = ExpandedQuery::new("Billing  SVC", &synonyms);
+        assert_eq!(phrase.text, "billing svc");
+        assert_eq!(phrase.whole, ["invoicer"]);
+        assert_eq!(phrase.search_text(), "Billing  SVC invoicer");
+    }
+
+    #[test]
+    fn rrf_of_nothing_is_empty() {
+        assert!(reciprocal_rank_fusion::<u32>(&[]).is_empty());
+        assert!(reciprocal_rank_fusion::<u32>(&[vec![], vec![]]).is_empty());
+    }
+}
@@ -1,27 +1,81 @@
 mod cli;
+mod confidence;
 mod config;
 mod db;
+#[cfg(feature = "semantic")]
 mod embedding;
+#[cfg(feature = "semantic-http")]
+mod embedding_http;
+mod fuzzy;
+mod git;
+mod learning;
+mod lexical;
+mod manifest;
+mod prompt;
+mod query;
+mod ranking;
 mod scanner;
 mod semantic;
+mod symbols;
+mod walk;
+mod workspace;
+
+#[cfg(all(
+    feature = "semantic",
+    not(any(feature = "semantic-local", feature = "semantic-http"))
+))]
+compile_error!(
+    "the semantic feature needs an embedding backend: enable semantic-local and/or semantic-http"
+);
 
 use anyhow::Result;
 use chrono::{Duration, Utc};
-use clap::Parser;
+use std::collections::HashMap;
 use std::path::Path;
-use std::process::Command;
 
-use cli::{Cli, Commands, SortOrder};
+use cli::{AliasAction, Cli, Commands, SortOrder, SynonymAction};
+use confidence::Decision;
 use config::Config;
 use db::{Database, Project};
+use prompt::Prompt;
+use query::ParsedQuery;
+use ranking::{RankOptions, ScoredProject};
 use scanner::Scanner;
 
+/// Exit code when nothing matches (also used for errors)
+const EXIT_NO_MATCH: i32 = 1;
+
+/// Exit code when several projects match too closely and the user
+/// cancels the prompt (2 is left to clap for usage errors)
+const EXIT_AMBIGUOUS: i32 = 3;
+
+/// Exit code when nothing matches but close names were suggested
+const EXIT_SUGGESTIONS: i32 = 4;
+
 fn main() -> Result<()> {
-    let cli = Cli::parse();
-    embedding::set_debug(cli.debug);
+    let cli = Cli::parse_args();
     let config = Config::load()?;
+    #[cfg(feature = "semantic")]
+    {
+        embedding::set_debug(cli.debug);
+        if let Err(e) = embedding::configure(&config) {
+            eprintln!("\x1b[31m✗\x1b[0m {:#}", e);
+            eprintln!(
+                "  Fix the embedding settings in {}",
+                Config::config_path()?.display()
+            );
+            std::process::exit(1);
+        }
+    }
     let mut db = Database::open()?;
 
+    // Vectors from an older model or text format are re-embedded right away
+    #[cfg(feature = "semantic")]
+    if semantic::sync_profile(&db)? {
+        eprintln!("\x1b[33m⚠\x1b[0m Embedding model or format changed, re-indexing projects");
+        semantic::index_projects(&db)?;
+    }
+
     // If a query is provided, search for it
     // Special case: "-" means show recent projects
     if !cli.query.is_empty() {
@@ -33,31 +87,25 @@ fn main() -> Result<()> {
     }
 
     match cli.command {
-        Some(Commands::Recent { limit }) => {
-            show_recent(limit, &config, &db)
-        }
-        Some(Commands::Stats) => {
-            show_stats(&db)
-        }
-        Some(Commands::Update { force }) => {
-            update_all(force, &config, &mut db)
-        }
-        Some(Commands::List { sort, limit, all, git }) => {
+        Some(Commands::Recent { limit }) => show_recent(limit, &config, &db),
+        Some(Commands::Stats) => show_stats(&db),
+        Some(Commands::Update { force }) => update_all(force, &config, &mut db),
+        Some(Commands::List {
+            sort,
+            limit,
+            all,
+            git,
+        }) => {
             let actual_limit = if all { usize::MAX } else { limit };
             list_projects(sort, actual_limit, git, &db)
         }
-        Some(Commands::Add { path }) => {
-            add_path(path, &mut Config::load()?)
-        }
-        Some(Commands::Remove { path }) => {
-            remove_path(path, &mut Config::load()?)
-        }
-        Some(Commands::Config) => {
-            show_config(&config)
-        }
-        Some(Commands::Test) => {
-            run_tests(&db)
-        }
+        Some(Commands::Add { path }) => add_path(path, &mut Config::load()?),
+        Some(Commands::Remove { path }) => remove_path(path, &mut Config::load()?),
+        Some(Commands::Config) => show_config(&config),
+        Some(Commands::Test) => run_tests(&config, &db),
+        Some(Commands::Alias { action }) => manage_alias(action, &config, &db),
+        Some(Commands::Learned { reset }) => show_learned(reset, &db),
+        Some(Commands::Synonyms { action }) => manage_synonyms(action, &mut Config::load()?),
         None => {
             // No command and no query - show help hint
             eprintln!("\x1b[33mUsage:\x1b[0m goto <query> or goto --help for more options");
@@ -66,38 +114,13 @@ fn main() -> Result<()> {
     }
 }
 
-/// Get git branch and dirty status for a project
-fn get_git_status(path: &Path) -> Option<(String, bool)> {
-    // Get current branch
-    let branch_output = Command::new("git")
-        .args(["-C", &path.to_string_lossy(), "rev-parse", "--abbrev-ref", "HEAD"])
-        .output()
-        .ok()?;
-
-    if !branch_output.status.success() {
-        return None;
-    }
-
-    let branch = String::from_utf8_lossy(&branch_output.stdout).trim().to_string();
-
-    // Check if dirty (has uncommitted changes)
-    let status_output = Command::new("git")
-        .args(["-C", &path.to_string_lossy(), "status", "--porcelain"])
-        .output()
-        .ok()?;
-
-    let is_dirty = !status_output.stdout.is_empty();
-
-    Some((branch, is_dirty))
-}
-
 /// Show recently accessed projects
 fn show_recent(limit: usize, _config: &Config, db: &Database) -> Result<()> {
     let mut projects = db.get_all_projects()?;
 
     // Filter to only accessed projects and sort by recency
     projects.retain(|p| p.access_count > 0);
-    projects.sort_by(|a, b| b.last_accessed.cmp(&a.last_accessed));
+    projects.sort_by_key(|p| std::cmp::Reverse(p.last_accessed));
 
     if projects.is_empty() {
         eprintln!("\x1b[33m⚠\x1b[0m No recently accessed projects.");
@@ -108,7 +131,7 @@ fn show_recent(limit: usize, _config: &Config, db: &Database) -> Result<()> {
     eprintln!("\x1b[36mRecent projects:\x1b[0m\n");
 
     for (i, project) in projects.iter().take(limit).enumerate() {
-        let git_info = get_git_status(&project.path)
+        let git_info = git::status(&project.path)
             .map(|(branch, dirty)| {
                 let dirty_marker = if dirty { "*" } else { "" };
                 format!(" \x1b[33m{}{}\x1b[0m", branch, dirty_marker)
@@ -145,19 +168,23 @@ fn show_stats(db: &Database) -> Result<()> {
 
     // Projects accessed in last 7 days
     let week_ago = Utc::now() - Duration::days(7);
-    let active_this_week: Vec<&Project> = accessed.iter()
+    let active_this_week: Vec<&Project> = accessed
+        .iter()
         .filter(|p| p.last_accessed > week_ago)
         .copied()
         .collect();
 
     // Top 5 most accessed
     let mut by_access = projects.clone();
-    by_access.sort_by(|a, b| b.access_count.cmp(&a.access_count));
+    by_access.sort_by_key(|p| std::cmp::Reverse(p.access_count));
 
     eprintln!("\x1b[36mProject Statistics\x1b[0m\n");
     eprintln!("  \x1b[90mTotal indexed:\x1b[0m     {}", total);
     eprintln!("  \x1b[90mEver accessed:\x1b[0m     {}", accessed.len());
-    eprintln!("  \x1b[90mActive this week:\x1b[0m  {}", active_this_week.len());
+    eprintln!(
+        "  \x1b[90mActive this week:\x1b[0m  {}",
+        active_this_week.len()
+    );
     eprintln!("  \x1b[90mTotal navigations:\x1b[0m {}", total_accesses);
 
     if !by_access.is_empty() && by_access[0].access_count > 0 {
@@ -165,8 +192,7 @@ fn show_stats(db: &Database) -> Result<()> {
         for project in by_access.iter().take(5).filter(|p| p.access_count > 0) {
             eprintln!(
                 "  \x1b[32m{:>3}x\x1b[0m \x1b[1m{}\x1b[0m",
-                project.access_count,
-                project.name
+                project.access_count, project.name
             );
         }
     }
@@ -175,11 +201,14 @@ fn show_stats(db: &Database) -> Result<()> {
         eprintln!("\n\x1b[36mActive this week:\x1b[0m\n");
         for project in active_this_week.iter().take(5) {
             let days_ago = (Utc::now() - project.last_accessed).num_days();
-            let when = if days_ago == 0 { "today".to_string() } else { format!("{}d ago", days_ago) };
+            let when = if days_ago == 0 {
+                "today".to_string()
+            } else {
+                format!("{}d ago", days_ago)
+            };
             eprintln!(
                 "  \x1b[90m{:>6}\x1b[0m \x1b[1m{}\x1b[0m",
-                when,
-                project.name
+                when, project.name
             );
         }
     }
@@ -187,63 +216,35 @@ fn show_stats(db: &Database) -> Result<()> {
     Ok(())
 }
 
-/// Minimum semantic score to accept a match (below this = no match)
-const SEMANTIC_MIN_THRESHOLD: f64 = 55.0;
-
-/// Boost score if project name contains query
-const SUBSTRING_BOOST: f32 = 20.0;
-
-/// Stronger boost if project name exactly matches query
-const EXACT_NAME_BOOST: f32 = 40.0;
-
-/// Smaller boost if query words found in metadata (README, folders, types)
-const METADATA_BOOST: f32 = 10.0;
-
-/// Calculate boosted score based on name and metadata matching
-fn calculate_boosted_score(
-    project_name: &str,
-    query_lower: &str,
-    base_score: f32,
-    embedded_text: Option<&str>,
-) -> f32 {
-    let name_lower = project_name.to_lowercase();
-
-    // Check for exact match first (strongest boost)
-    if name_lower == query_lower {
-        return (base_score + EXACT_NAME_BOOST).min(100.0);
-    }
-
-    // Check if name contains the full query
-    if name_lower.contains(query_lower) {
-        return (base_score + SUBSTRING_BOOST).min(100.0);
-    }
-
-    // Check if name contains ALL significant words from the query (3+ chars)
-    let query_words: Vec<&str> = query_lower
-        .split_whitespace()
-        .filter(|w| w.len() >= 3)
-        .collect();
-
-    if !query_words.is_empty() {
-        let all_words_match = query_words.iter().all(|w| name_lower.contains(*w));
-        if all_words_match {
-            return (base_score + SUBSTRING_BOOST).min(100.0);
-        }
+fn find_project(
+    query: &str,
+    show_all: bool,
+    limit: usize,
+    cd_only: bool,
+    config: &Config,
+    db: &Database,
+) -> Result<()> {
+    let cwd = std::env::current_dir().ok();
 
-        // Check if ALL query words appear in embedded metadata
-        if let Some(text) = embedded_text {
-            let text_lower = text.to_lowercase();
-            let all_in_metadata = query_words.iter().all(|w| text_lower.contains(*w));
-            if all_in_metadata {
-                return (base_score + METADATA_BOOST).min(100.0);
-            }
+    // Step 0: Aliases resolve before any search (they work without any indexed project)
+    if let Some(target) = db.get_alias(query.trim())? {
+        if !target.is_dir() {
+            eprintln!(
+                "\x1b[31m✗\x1b[0m Alias '\x1b[1m{}\x1b[0m' points to a missing directory: {}",
+                query.trim(),
+                target.display()
+            );
+            eprintln!(
+                "  Update it with \x1b[1mgoto alias set {} <path>\x1b[0m",
+                query.trim()
+            );
+            std::process::exit(1);
         }
+        db.mark_accessed(&target)?;
+        emit_jump(&target, cd_only, config);
+        return Ok(());
     }
 
-    base_score
-}
-
-fn find_project(query: &str, show_all: bool, limit: usize, cd_only: bool, config: &Config, db: &Database) -> Result<()> {
     let projects = db.get_all_projects()?;
 
     if projects.is_empty() {
@@ -252,154 +253,353 @@ fn find_project(query: &str, show_all: bool, limit: usize, cd_only: bool, config
         std::process::exit(1);
     }
 
-    // If show_all, just display semantic matches
+    // `repo/member` jumps straight into a workspace member
+    let trimmed = query.trim();
+    if !trimmed.contains(char::is_whitespace) && !trimmed.contains(':') {
+        if let Some(member) = workspace::resolve_member(trimmed, &projects) {
+            db.mark_accessed(&member.path)?;
+            emit_jump(&member.path, cd_only, config);
+            return Ok(());
+        }
+    }
+
+    let parsed = parse_query(query, cwd.as_deref());
+
+    // If show_all, display fuzzy + semantic matches
     if show_all {
-        return show_all_matches(query, limit, db);
+        return show_all_matches(&parsed, limit, &projects, cwd.as_deref(), config, db);
     }
 
-    // Step 1: Check for exact name match (fast path)
-    let query_lower = query.to_lowercase();
-    if let Some(exact) = projects.iter().find(|p| p.name.to_lowercase() == query_lower) {
-        db.mark_accessed(&exact.path)?;
-        println!("{}", exact.path.display());
-        if !cd_only {
-            if let Some(cmd) = &config.post_command {
-                eprintln!("__GOTO_POST_CMD__:{}", cmd);
-            }
+    let current = ranking::current_project(cwd.as_deref(), &projects);
+
+    // Step 1: Check for a unique exact name match (fast path)
+    // Duplicates fall through to ranking so the one nearest to the cwd wins,
+    // and the project we're already in is skipped in favour of the next one
+    let expanded = lexical::ExpandedQuery::new(&parsed.text, &config.synonyms);
+    let query_lower = fuzzy::fold(&parsed.text);
+    let exact_names = expanded.whole_alternatives();
+    let exact_matches: Vec<&Project> = projects
+        .iter()
+        .filter(|p| {
+            let name = fuzzy::fold(&p.name);
+            name == query_lower || exact_names.contains(&name.as_str())
+        })
+        .filter(|p| Some(p.path.as_path()) != current)
+        .collect();
+    if let [exact] = exact_matches.as_slice() {
+        if !parsed.has_filters() {
+            db.mark_accessed(&exact.path)?;
+            learning::remember_jump(db, &parsed.text, &exact.path, cwd.as_deref())?;
+            emit_jump(&exact.path, cd_only, config);
+            return Ok(());
         }
-        return Ok(());
     }
 
-    // Step 2: Use semantic search
-    let best_project = find_best_match(query, &projects, db)?;
+    // Step 2: Fuzzy name matching, falling back to semantic search when ambiguous
+    let ranked = rank_for_jump(&parsed, &projects, cwd.as_deref(), current, config, db)?;
 
-    match best_project {
-        Some((project, score, is_semantic)) => {
-            // Mark as accessed
-            db.mark_accessed(&project.path)?;
-
-            // Output path for the shell function to cd to
-            println!("{}", project.path.display());
+    // Suggestions come from the projects the filters and exclusions let through
+    let passes_filters = parsed.static_filter(db, &config.scan_paths)?;
+    let allowed: Vec<Project> = projects
+        .iter()
+        .filter(|p| passes_filters(p))
+        .cloned()
+        .collect();
 
-            // Show match info on stderr (doesn't interfere with path)
-            if is_semantic {
-                eprintln!(
-                    "\x1b[35m◆\x1b[0m \x1b[1m{}\x1b[0m \x1b[90m(semantic: {:.0}%)\x1b[0m",
-                    project.name, score
-                );
-            }
+    match confidence::decide(&parsed.text, ranked, &allowed, config.frecency_weight) {
+        Decision::Jump(best) => {
+            jump_to_match(&best, &parsed.text, cwd.as_deref(), cd_only, config, db)?
+        }
+        Decision::Ask(candidates) => {
+            let prompt = if config.interactive {
+                prompt::choose(query, &candidates)?
+            } else {
+                Prompt::Unavailable
+            };
 
-            // Output post command if configured
-            if !cd_only {
-                if let Some(cmd) = &config.post_command {
-                    eprintln!("__GOTO_POST_CMD__:{}", cmd);
+            match prompt {
+                // The pick is recorded against the candidates in the order shown,
+                // so choosing another than the first adjusts future ranking
+                Prompt::Picked(i) => {
+                    learning::record_selection(
+                        db,
+                        &parsed.text,
+                        &candidates,
+                        &candidates[i].project.path,
+                    )?;
+                    return jump_to_match(
+                        &candidates[i],
+                        &parsed.text,
+                        cwd.as_deref(),
+                        cd_only,
+                        config,
+                        db,
+                    );
+                }
+                Prompt::Cancelled => std::process::exit(EXIT_AMBIGUOUS),
+                // No terminal to ask on: go to the best match, and say what else matched
+                Prompt::Unavailable => {
+                    let others: Vec<String> = candidates[1..]
+                        .iter()
+                        .map(|c| {
+                            format!(
+                                "\x1b[1m{}\x1b[0m \x1b[90m({})\x1b[0m",
+                                c.project.display_name(),
+                                short_path(&c.project.path)
+                            )
+                        })
+                        .collect();
+                    jump_to_match(
+                        &candidates[0],
+                        &parsed.text,
+                        cwd.as_deref(),
+                        cd_only,
+                        config,
+                        db,
+                    )?;
+                    eprintln!("  \x1b[90mAlso matching:\x1b[0m {}", others.join(", "));
+                    return Ok(());
                 }
             }
         }
-        None => {
+        Decision::Suggest(suggestions) => {
             eprintln!("\x1b[31m✗\x1b[0m No projects matching '\x1b[1m{query}\x1b[0m'");
-            eprintln!("  Try a different query or run \x1b[1mgoto list\x1b[0m to see all projects.");
-            std::process::exit(1);
+            // Names shared by several suggestions are told apart by their path
+            let names: Vec<String> = suggestions
+                .iter()
+                .map(|p| {
+                    let name = p.display_name();
+                    if suggestions
+                        .iter()
+                        .filter(|other| other.display_name() == name)
+                        .count()
+                        > 1
+                    {
+                        format!(
+                            "\x1b[1m{}\x1b[0m \x1b[90m({})\x1b[0m",
+                            name,
+                            short_path(&p.path)
+                        )
+                    } else {
+                        format!("\x1b[1m{}\x1b[0m", name)
+                    }
+                })
+                .collect();
+            eprintln!("  Did you mean {}?", names.join(", "));
+            std::process::exit(EXIT_SUGGESTIONS);
+        }
+        Decision::NoMatch => {
+            eprintln!("\x1b[31m✗\x1b[0m No projects matching '\x1b[1m{query}\x1b[0m'");
+            eprintln!(
+                "  Try a different query or run \x1b[1mgoto list\x1b[0m to see all projects."
+            );
+            std::process::exit(EXIT_NO_MATCH);
         }
     }
 
     Ok(())
 }
 
-/// Find the best match using semantic search with substring boost
-fn find_best_match(
+/// Jump to a ranked match
+fn jump_to_match(
+    chosen: &ScoredProject,
     query: &str,
-    _projects: &[Project],
+    cwd: Option<&Path>,
+    cd_only: bool,
+    config: &Config,
     db: &Database,
-) -> Result<Option<(Project, f64, bool)>> {
-    let (indexed, _) = db.embedding_stats()?;
-    if indexed == 0 {
-        return Ok(None);
-    }
+) -> Result<()> {
+    let project = &chosen.project;
 
-    // Get more results to find matching names
-    if let Ok(results) = semantic::semantic_search(db, query, 10) {
-        let query_lower = query.to_lowercase();
-
-        // Apply name and metadata-based boost and find best
-        let best = results
-            .into_iter()
-            .map(|(project, score)| {
-                let embedded_text = db.get_embedded_text(&project.path).ok().flatten();
-                let boosted = calculate_boosted_score(
-                    &project.name,
-                    &query_lower,
-                    score,
-                    embedded_text.as_deref(),
-                );
-                (project, boosted)
-            })
-            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+    // Mark as accessed and remember the jump in case the next query shows it missed
+    db.mark_accessed(&project.path)?;
+    learning::remember_jump(db, query, &project.path, cwd)?;
 
-        if let Some((project, score)) = best {
-            if score as f64 >= SEMANTIC_MIN_THRESHOLD {
-                return Ok(Some((project, score as f64, true)));
-            }
+    // Show match info on stderr (doesn't interfere with path)
+    eprintln!(
+        "\x1b[35m◆\x1b[0m \x1b[1m{}\x1b[0m \x1b[90m({}: {:.0}%)\x1b[0m",
+        project.display_name(),
+        chosen.match_kind(),
+        chosen.relevance
+    );
+
+    emit_jump(&project.path, cd_only, config);
+    Ok(())
+}
+
+/// Output the path for the shell function to cd to, plus the post command if configured
+fn emit_jump(path: &Path, cd_only: bool, config: &Config) {
+    println!("{}", path.display());
+    if !cd_only {
+        if let Some(cmd) = &config.post_command {
+            eprintln!("__GOTO_POST_CMD__:{}", cmd);
         }
     }
+}
+
+/// Path with ~ for the home directory
+fn short_path(path: &Path) -> String {
+    let home = dirs::home_dir().unwrap_or_default();
+    match path.strip_prefix(&home) {
+        Ok(rest) => format!("~/{}", rest.display()),
+        Err(_) => path.display().to_string(),
+    }
+}
 
-    Ok(None)
+/// Split inline operators (lang:, under:, ...) from the query text, exiting on invalid ones
+fn parse_query(query: &str, cwd: Option<&Path>) -> ParsedQuery {
+    match ParsedQuery::parse(query, cwd) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("\x1b[31m✗\x1b[0m {e}");
+            std::process::exit(1);
+        }
+    }
 }
 
-/// Show semantic search results with substring boost
-fn show_all_matches(query: &str, limit: usize, db: &Database) -> Result<()> {
-    let (indexed, _) = db.embedding_stats()?;
-    if indexed == 0 {
-        eprintln!("\x1b[31m✗\x1b[0m No projects indexed for semantic search.");
-        eprintln!("  Run \x1b[1mgoto update\x1b[0m to index projects.");
-        std::process::exit(1);
+/// Rank candidates for a jump, best first, by fusing fuzzy name matching with semantic search
+///
+/// The current project is left out when there is another acceptable
+/// match, so `goto api` from inside the best match moves on.
+fn rank_for_jump(
+    query: &ParsedQuery,
+    projects: &[Project],
+    cwd: Option<&Path>,
+    current: Option<&Path>,
+    config: &Config,
+    db: &Database,
+) -> Result<Vec<ScoredProject>> {
+    let options = RankOptions {
+        limit: 10,
+        use_semantic: true,
+        allow_fuzzy_shortcut: true,
+        cwd,
+    };
+    let ranked = ranking::rank_projects(query, projects, db, config, options)?;
+
+    let is_current = |c: &ScoredProject| Some(c.project.path.as_path()) == current;
+    let other_acceptable = ranked
+        .iter()
+        .any(|c| c.relevance as f64 >= ranking::SEMANTIC_MIN_THRESHOLD && !is_current(c));
+    if !other_acceptable {
+        return Ok(ranked);
     }
 
+    Ok(ranked.into_iter().filter(|c| !is_current(c)).collect())
+}
+
+/// Acceptable matches only (relevance above the threshold), best first
+fn find_best_matches(
+    query: &ParsedQuery,
+    projects: &[Project],
+    cwd: Option<&Path>,
+    config: &Config,
+    db: &Database,
+) -> Result<Vec<ScoredProject>> {
+    Ok(rank_for_jump(
+        query,
+        projects,
+        cwd,
+        ranking::current_project(cwd, projects),
+        config,
+        db,
+    )?
+    .into_iter()
+    .filter(|c| c.relevance as f64 >= ranking::SEMANTIC_MIN_THRESHOLD)
+    .collect())
+}
+
+/// Show fuzzy + semantic search results with name boost
+fn show_all_matches(
+    query: &ParsedQuery,
+    limit: usize,
+    projects: &[Project],
+    cwd: Option<&Path>,
+    config: &Config,
+    db: &Database,
+) -> Result<()> {
+    let current = ranking::current_project(cwd, projects);
+
     // Fetch more than needed to allow for boosting reordering
     let fetch_limit = (limit * 2).max(20);
-    if let Ok(results) = semantic::semantic_search(db, query, fetch_limit) {
-        let query_lower = query.to_lowercase();
-
-        // Boost scores for name and metadata matches and re-sort
-        let mut boosted: Vec<_> = results
-            .into_iter()
-            .map(|(project, score)| {
-                let embedded_text = db.get_embedded_text(&project.path).ok().flatten();
-                let boosted_score = calculate_boosted_score(
-                    &project.name,
-                    &query_lower,
-                    score,
-                    embedded_text.as_deref(),
-                );
-                (project, boosted_score)
-            })
-            .collect();
+    let options = RankOptions {
+        limit: fetch_limit,
+        use_semantic: true,
+        allow_fuzzy_shortcut: false,
+        cwd,
+    };
+    let boosted = ranking::rank_projects(query, projects, db, config, options)?;
+
+    if boosted.is_empty() {
+        let (indexed, _) = db.embedding_stats()?;
+        if indexed == 0 {
+            eprintln!(
+                "\x1b[31m✗\x1b[0m No fuzzy matches, and no projects indexed for semantic search."
+            );
+            eprintln!("  Run \x1b[1mgoto update\x1b[0m to index projects.");
+            std::process::exit(1);
+        }
+    }
 
-        boosted.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    // Find duplicate names to show parent dir (always shown when querying by path)
+    let names: Vec<_> = boosted
+        .iter()
+        .take(limit)
+        .map(|c| &c.project.name)
+        .collect();
+    let path_query = query.text.contains('/');
+
+    for (i, candidate) in boosted.iter().take(limit).enumerate() {
+        let project = &candidate.project;
+        let has_duplicate = names.iter().filter(|n| **n == &project.name).count() > 1;
+        let display_name = if has_duplicate || path_query {
+            format!(
+                "{} \x1b[90m({})\x1b[0m",
+                project.display_name(),
+                short_path(&project.path)
+            )
+        } else {
+            project.display_name()
+        };
 
-        // Find duplicate names to show parent dir
-        let names: Vec<_> = boosted.iter().take(limit).map(|(p, _)| &p.name).collect();
+        let mut extra_info = String::new();
+        if candidate.frecency >= 0.5 {
+            extra_info.push_str(&format!(
+                " \x1b[33m+{:.0} frecency\x1b[0m",
+                candidate.frecency
+            ));
+        }
+        if candidate.learned.abs() >= 0.5 {
+            extra_info.push_str(&format!(
+                " \x1b[32m{:+.0} learned\x1b[0m",
+                candidate.learned
+            ));
+        }
+        if candidate.proximity >= 0.5 {
+            extra_info.push_str(&format!(
+                " \x1b[36m+{:.0} nearby\x1b[0m",
+                candidate.proximity
+            ));
+        }
+        if Some(project.path.as_path()) == current {
+            extra_info.push_str(" \x1b[90m(current)\x1b[0m");
+        }
 
-        for (i, (project, score)) in boosted.iter().take(limit).enumerate() {
-            let has_duplicate = names.iter().filter(|n| **n == &project.name).count() > 1;
-            let display_name = if has_duplicate {
-                // Show full path with ~ for home directory
-                let home = dirs::home_dir().unwrap_or_default();
-                let path_str = if project.path.starts_with(&home) {
-                    format!("~/{}", project.path.strip_prefix(&home).unwrap().display())
-                } else {
-                    project.path.display().to_string()
-                };
-                format!("{} \x1b[90m({})\x1b[0m", project.name, path_str)
-            } else {
-                project.name.clone()
-            };
+        eprintln!(
+            "\x1b[35m{}.\x1b[0m \x1b[1m{}\x1b[0m \x1b[90m({:.0}%)\x1b[0m{}",
+            i + 1,
+            display_name,
+            candidate.relevance,
+            extra_info
+        );
 
+        // Where the query matched in the code, when it names a symbol
+        if let Some(symbol) = symbols::best_match(&query.text, &db.get_symbols(&project.path)?) {
             eprintln!(
-                "\x1b[35m{}.\x1b[0m \x1b[1m{}\x1b[0m \x1b[90m({:.0}%)\x1b[0m",
-                i + 1,
-                display_name,
-                score
+                "   \x1b[90m↳ {} {} in {}\x1b[0m",
+                symbol.kind.as_str(),
+                symbol.name,
+                symbol.file.display()
             );
         }
     }
@@ -407,6 +607,43 @@ fn show_all_matches(query: &str, limit: usize, db: &Database) -> Result<()> {
     Ok(())
 }
 
+/// Show (or reset) what ranking has learned from past selections
+fn show_learned(reset: bool, db: &Database) -> Result<()> {
+    if reset {
+        let removed = db.clear_learning()?;
+        eprintln!(
+            "\x1b[32m✓\x1b[0m Forgot {} selections and reset ranking weights",
+            removed
+        );
+        return Ok(());
+    }
+
+    let weights = learning::load_weights(db)?;
+    eprintln!("\x1b[36mBoost weights\x1b[0m \x1b[90m(1.00 = default)\x1b[0m\n");
+    for (name, value) in weights.entries() {
+        eprintln!("  \x1b[90m{:<11}\x1b[0m {:.2}", name, value);
+    }
+
+    let learned = db.get_learned_queries(20)?;
+    if learned.is_empty() {
+        eprintln!("\n\x1b[90mNo selections recorded yet.\x1b[0m");
+        return Ok(());
+    }
+
+    eprintln!("\n\x1b[36mLearned queries:\x1b[0m\n");
+    for (query, path, count) in learned {
+        eprintln!(
+            "  \x1b[32m{:>3}x\x1b[0m \x1b[1m{}\x1b[0m → \x1b[90m{}\x1b[0m",
+            count,
+            query,
+            path.display()
+        );
+    }
+
+    eprintln!("\n\x1b[90mTip: goto learned --reset to forget everything\x1b[0m");
+    Ok(())
+}
+
 /// Test case structure
 #[derive(Debug, serde::Deserialize)]
 struct TestCase {
@@ -416,15 +653,59 @@ struct TestCase {
     top_n: usize,
 }
 
-fn default_top_n() -> usize { 3 }
+fn default_top_n() -> usize {
+    3
+}
 
 #[derive(Debug, serde::Deserialize)]
 struct TestFile {
     tests: Vec<TestCase>,
 }
 
+/// index_info key holding the outcome of the previous `goto test`
+const LAST_TEST_RUN_KEY: &str = "last_test_run";
+
+/// Outcome of one test case, kept to compare runs
+struct TestOutcome {
+    passed: bool,
+    top: String,
+}
+
+/// Previous run: embedding profile it ran with, and outcome per query.
+/// Stored as the profile on the first line, then `passed<TAB>query<TAB>top`.
+fn load_last_test_run(db: &Database) -> Result<Option<(String, HashMap<String, TestOutcome>)>> {
+    let Some(stored) = db.get_info(LAST_TEST_RUN_KEY)? else {
+        return Ok(None);
+    };
+    let mut lines = stored.lines();
+    let profile = lines.next().unwrap_or_default().to_string();
+    let outcomes = lines
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            let passed = parts.next()? == "1";
+            let query = parts.next()?.to_string();
+            let top = parts.next().unwrap_or_default().to_string();
+            Some((query, TestOutcome { passed, top }))
+        })
+        .collect();
+    Ok(Some((profile, outcomes)))
+}
+
+fn save_last_test_run(db: &Database, outcomes: &[(String, TestOutcome)]) -> Result<()> {
+    let mut stored = semantic::profile_key();
+    for (query, outcome) in outcomes {
+        stored.push_str(&format!(
+            "\n{}\t{}\t{}",
+            u8::from(outcome.passed),
+            query.replace(['\t', '\n'], " "),
+            outcome.top
+        ));
+    }
+    db.set_info(LAST_TEST_RUN_KEY, &stored)
+}
+
 /// Run ranking tests from config file
-fn run_tests(db: &Database) -> Result<()> {
+fn run_tests(config: &Config, db: &Database) -> Result<()> {
     let config_dir = directories::ProjectDirs::from("", "", "goto")
         .map(|d| d.config_dir().to_path_buf())
         .unwrap_or_else(|| dirs::home_dir().unwrap().join(".config/goto"));
@@ -453,7 +734,10 @@ top_n = 5
 "#;
         std::fs::create_dir_all(&config_dir)?;
         std::fs::write(&test_file, example)?;
-        eprintln!("\x1b[32m✓\x1b[0m Created example test file: {}", test_file.display());
+        eprintln!(
+            "\x1b[32m✓\x1b[0m Created example test file: {}",
+            test_file.display()
+        );
         eprintln!("  Edit it and run \x1b[1mgoto test\x1b[0m again");
         return Ok(());
     }
@@ -467,72 +751,123 @@ top_n = 5
         std::process::exit(1);
     }
 
+    let projects = db.get_all_projects()?;
+    let previous = load_last_test_run(db)?;
+    let mut outcomes: Vec<(String, TestOutcome)> = Vec::new();
     let mut passed = 0;
     let mut failed = 0;
 
     for test in &tests.tests {
-        // Run semantic search with name-based boost
-        let results = semantic::semantic_search(db, &test.query, 20)?;
-        let query_lower = test.query.to_lowercase();
-
-        let mut boosted: Vec<_> = results
-            .into_iter()
-            .map(|(project, score)| {
-                let embedded_text = db.get_embedded_text(&project.path).ok().flatten();
-                let boosted_score = calculate_boosted_score(
-                    &project.name,
-                    &query_lower,
-                    score,
-                    embedded_text.as_deref(),
-                );
-                (project, boosted_score)
-            })
-            .collect();
-
-        boosted.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        // Run fuzzy + semantic search with name-based boost
+        let options = RankOptions {
+            limit: 20,
+            use_semantic: true,
+            allow_fuzzy_shortcut: false,
+            cwd: None,
+        };
+        let parsed = parse_query(&test.query, None);
+        let boosted = ranking::rank_projects(&parsed, &projects, db, config, options)?;
 
-        let top_names: Vec<_> = boosted.iter().take(test.top_n).map(|(p, _)| &p.name).collect();
+        let top_names: Vec<_> = boosted
+            .iter()
+            .take(test.top_n)
+            .map(|c| &c.project.name)
+            .collect();
 
         // Check if any expected result is in top N (exact match)
         let mut found: Vec<&str> = vec![];
         let mut missing: Vec<&str> = vec![];
 
         for exp in &test.expected {
-            if top_names.iter().any(|n| *n == exp) {
+            if top_names.contains(&exp) {
                 found.push(exp);
             } else {
                 missing.push(exp);
             }
         }
 
-        if missing.is_empty() {
+        let top = top_names.first().map(|s| s.as_str()).unwrap_or("?");
+        let outcome = TestOutcome {
+            passed: missing.is_empty(),
+            top: top.to_string(),
+        };
+
+        // What changed since the previous run
+        let change = match previous
+            .as_ref()
+            .map(|(_, outcomes)| outcomes.get(&test.query))
+        {
+            Some(Some(before)) => match (before.passed, outcome.passed) {
+                (false, true) => " \x1b[32m[fixed]\x1b[0m".to_string(),
+                (true, false) => format!(" \x1b[31m[regressed, was {}]\x1b[0m", before.top),
+                _ if before.top != outcome.top => format!(" \x1b[33m[was {}]\x1b[0m", before.top),
+                _ => String::new(),
+            },
+            Some(None) => " \x1b[90m[new]\x1b[0m".to_string(),
+            None => String::new(),
+        };
+
+        if outcome.passed {
             passed += 1;
             eprintln!(
-                "\x1b[32m✓\x1b[0m \"{}\" → {} \x1b[90m(found: {})\x1b[0m",
+                "\x1b[32m✓\x1b[0m \"{}\" → {} \x1b[90m(found: {})\x1b[0m{}",
                 test.query,
-                top_names.first().map(|s| s.as_str()).unwrap_or("?"),
-                found.join(", ")
+                top,
+                found.join(", "),
+                change
             );
         } else {
             failed += 1;
             eprintln!(
-                "\x1b[31m✗\x1b[0m \"{}\" → {} \x1b[90m(missing: {})\x1b[0m",
+                "\x1b[31m✗\x1b[0m \"{}\" → {} \x1b[90m(missing: {})\x1b[0m{}",
                 test.query,
-                top_names.first().map(|s| s.as_str()).unwrap_or("?"),
-                missing.join(", ")
+                top,
+                missing.join(", "),
+                change
             );
             // Show actual top results
-            for (i, (p, score)) in boosted.iter().take(test.top_n).enumerate() {
-                eprintln!("    {}. {} ({:.0}%)", i + 1, p.name, score);
+            for (i, c) in boosted.iter().take(test.top_n).enumerate() {
+                eprintln!("    {}. {} ({:.0}%)", i + 1, c.project.name, c.relevance);
             }
         }
+        outcomes.push((test.query.clone(), outcome));
     }
 
     eprintln!();
+    if let Some((profile, before)) = &previous {
+        let before_passed = outcomes
+            .iter()
+            .filter(|(query, _)| before.get(query).map(|o| o.passed).unwrap_or(false))
+            .count();
+        let profile_note = if *profile == semantic::profile_key() {
+            String::new()
+        } else {
+            format!(" ({} → {})", profile, semantic::profile_key())
+        };
+        eprintln!(
+            "\x1b[90mPrevious run: {}/{} passed, now {}/{}{}\x1b[0m",
+            before_passed,
+            outcomes.len(),
+            passed,
+            outcomes.len(),
+            profile_note
+        );
+    } else {
+        eprintln!(
+            "\x1b[90mNo previous run to compare with ({}); this one is the baseline for the next goto test\x1b[0m",
+            semantic::profile_key()
+        );
+    }
+    save_last_test_run(db, &outcomes)?;
+
     if failed == 0 {
         eprintln!("\x1b[32m✓ All {} tests passed\x1b[0m", passed);
     } else {
-        eprintln!("\x1b[31m✗ {}/{} tests failed\x1b[0m", failed, passed + failed);
+        eprintln!(
+            "\x1b[31m✗ {}/{} tests failed\x1b[0m",
+            failed,
+            passed + failed
+        );
         std::process::exit(1);
     }
 
@@ -563,12 +898,27 @@ fn update_all(force: bool, config: &Config, db: &mut Database) -> Result<()> {
         db.clear_embeddings()?;
     }
 
-    let count = semantic::index_projects(db)?;
+    let report = semantic::index_projects(db)?;
 
-    if count > 0 {
-        eprintln!("\x1b[32m✓\x1b[0m Indexed \x1b[1m{}\x1b[0m projects for semantic search", count);
-    } else {
-        eprintln!("\x1b[32m✓\x1b[0m All projects already indexed");
+    if report.added > 0 {
+        let index = if cfg!(feature = "semantic") && report.embedding_error.is_none() {
+            "semantic"
+        } else {
+            "full-text"
+        };
+        eprintln!(
+            "\x1b[32m✓\x1b[0m Indexed \x1b[1m{}\x1b[0m projects for {} search",
+            report.added, index
+        );
+    }
+    if report.refreshed > 0 {
+        eprintln!(
+            "\x1b[32m✓\x1b[0m Refreshed \x1b[1m{}\x1b[0m changed projects",
+            report.refreshed
+        );
+    }
+    if report.added == 0 && report.refreshed == 0 {
+        eprintln!("\x1b[32m✓\x1b[0m All projects up to date");
     }
 
     Ok(())
@@ -579,7 +929,7 @@ fn list_projects(sort: SortOrder, limit: usize, show_git: bool, db: &Database) -
 
     match sort {
         SortOrder::Recent => {
-            projects.sort_by(|a, b| b.last_accessed.cmp(&a.last_accessed));
+            projects.sort_by_key(|p| std::cmp::Reverse(p.last_accessed));
         }
         SortOrder::Frecency => {
             projects.sort_by(|a, b| {
@@ -599,12 +949,24 @@ fn list_projects(sort: SortOrder, limit: usize, show_git: bool, db: &Database) -
         return Ok(());
     }
 
+    let aliases = db.get_aliases()?;
+
     let total = projects.len();
-    eprintln!("\x1b[36mProjects\x1b[0m (showing {}/{}):\n", std::cmp::min(limit, total), total);
+    eprintln!(
+        "\x1b[36mProjects\x1b[0m (showing {}/{}):\n",
+        std::cmp::min(limit, total),
+        total
+    );
 
     for project in projects.iter().take(limit) {
+        let alias_info: String = aliases
+            .iter()
+            .filter(|(_, path)| *path == project.path)
+            .map(|(name, _)| format!(" \x1b[36m@{}\x1b[0m", name))
+            .collect();
+
         let git_info = if show_git {
-            get_git_status(&project.path)
+            git::status(&project.path)
                 .map(|(branch, dirty)| {
                     let dirty_marker = if dirty { "\x1b[31m*\x1b[0m" } else { "" };
                     format!(" \x1b[33m{}\x1b[0m{}", branch, dirty_marker)
@@ -615,48 +977,237 @@ fn list_projects(sort: SortOrder, limit: usize, show_git: bool, db: &Database) -
         };
 
         println!(
-            "  \x1b[1m{}\x1b[0m{} \x1b[90m{}\x1b[0m",
-            project.name,
+            "  \x1b[1m{}\x1b[0m{}{} \x1b[90m{}\x1b[0m",
+            project.display_name(),
+            alias_info,
             git_info,
             project.path.display()
         );
     }
 
+    // Aliases pointing outside indexed projects (docs folders, shared drives...)
+    let other_aliases: Vec<_> = aliases
+        .iter()
+        .filter(|(_, path)| !projects.iter().any(|p| p.path == *path))
+        .collect();
+    if !other_aliases.is_empty() {
+        eprintln!("\n\x1b[36mOther aliases:\x1b[0m\n");
+        for (name, path) in other_aliases {
+            println!(
+                "  \x1b[36m@{}\x1b[0m \x1b[90m{}\x1b[0m",
+                name,
+                path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Add, remove or list query synonyms
+fn manage_synonyms(action: Option<SynonymAction>, config: &mut Config) -> Result<()> {
+    match action.unwrap_or(SynonymAction::Ls) {
+        SynonymAction::Add { term, expansions } => {
+            if term.contains(char::is_whitespace) {
+                eprintln!(
+                    "\x1b[31m✗\x1b[0m Invalid term '\x1b[1m{}\x1b[0m' (no spaces)",
+                    term
+                );
+                std::process::exit(1);
+            }
+            config.add_synonyms(&term, &expansions)?;
+            eprintln!(
+                "\x1b[32m✓\x1b[0m \x1b[1m{}\x1b[0m → {}",
+                term,
+                expansions.join(", ")
+            );
+        }
+        SynonymAction::Rm { term, expansion } => {
+            if config.remove_synonym(&term, expansion.as_deref())? {
+                eprintln!(
+                    "\x1b[32m✓\x1b[0m Removed \x1b[1m{}\x1b[0m",
+                    expansion.as_deref().unwrap_or(&term)
+                );
+            } else {
+                eprintln!(
+                    "\x1b[33m⚠\x1b[0m No synonym \x1b[1m{}\x1b[0m",
+                    expansion.as_deref().unwrap_or(&term)
+                );
+            }
+        }
+        SynonymAction::Ls => {
+            if config.synonyms.is_empty() {
+                eprintln!("\x1b[90mNo synonyms yet. Add one with goto synonyms add <term> <expansion>...\x1b[0m");
+                return Ok(());
+            }
+
+            eprintln!("\x1b[36mSynonyms:\x1b[0m\n");
+            for (term, expansions) in &config.synonyms {
+                eprintln!(
+                    "  \x1b[1m{:<12}\x1b[0m \x1b[90m→\x1b[0m {}",
+                    term,
+                    expansions.join(", ")
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Create, remove or list aliases
+fn manage_alias(action: AliasAction, config: &Config, db: &Database) -> Result<()> {
+    match action {
+        AliasAction::Set { name, target } => {
+            if name.contains(char::is_whitespace) || name == "-" {
+                eprintln!(
+                    "\x1b[31m✗\x1b[0m Invalid alias name '\x1b[1m{}\x1b[0m' (no spaces)",
+                    name
+                );
+                std::process::exit(1);
+            }
+
+            let path = match resolve_alias_target(&target, config, db)? {
+                Some(path) => path,
+                None => {
+                    eprintln!("\x1b[31m✗\x1b[0m '\x1b[1m{}\x1b[0m' is neither a directory nor a known project", target);
+                    std::process::exit(1);
+                }
+            };
+
+            db.set_alias(&name, &path)?;
+            eprintln!(
+                "\x1b[32m✓\x1b[0m \x1b[1m{}\x1b[0m → {}",
+                name,
+                path.display()
+            );
+        }
+        AliasAction::Rm { name } => {
+            if db.remove_alias(&name)? {
+                eprintln!("\x1b[32m✓\x1b[0m Removed alias \x1b[1m{}\x1b[0m", name);
+            } else {
+                eprintln!("\x1b[33m⚠\x1b[0m No alias named \x1b[1m{}\x1b[0m", name);
+            }
+        }
+        AliasAction::Ls { names } => {
+            let aliases = db.get_aliases()?;
+            if names {
+                for (name, _) in &aliases {
+                    println!("{}", name);
+                }
+                return Ok(());
+            }
+
+            if aliases.is_empty() {
+                eprintln!("\x1b[90mNo aliases yet. Create one with goto alias set <name> <path-or-project>\x1b[0m");
+                return Ok(());
+            }
+
+            eprintln!("\x1b[36mAliases:\x1b[0m\n");
+            for (name, path) in &aliases {
+                let missing = if path.is_dir() {
+                    ""
+                } else {
+                    " \x1b[31m(missing)\x1b[0m"
+                };
+                eprintln!(
+                    "  \x1b[1m{:<12}\x1b[0m \x1b[90m{}\x1b[0m{}",
+                    name,
+                    path.display(),
+                    missing
+                );
+            }
+        }
+    }
+
     Ok(())
 }
 
+/// Resolve an alias target: an existing directory, or a project found by name/query
+fn resolve_alias_target(
+    target: &str,
+    config: &Config,
+    db: &Database,
+) -> Result<Option<std::path::PathBuf>> {
+    let expanded = match target.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => std::path::PathBuf::from(target),
+    };
+    if expanded.is_dir() {
+        return Ok(Some(expanded.canonicalize()?));
+    }
+
+    let projects = db.get_all_projects()?;
+    let target_lower = fuzzy::fold(target);
+    if let Some(exact) = projects
+        .iter()
+        .find(|p| fuzzy::fold(&p.name) == target_lower)
+    {
+        return Ok(Some(exact.path.clone()));
+    }
+
+    let parsed = parse_query(target, None);
+    let matches = find_best_matches(&parsed, &projects, None, config, db)?;
+    Ok(matches.into_iter().next().map(|m| m.project.path))
+}
+
 fn add_path(path: std::path::PathBuf, config: &mut Config) -> Result<()> {
     let canonical = path.canonicalize()?;
     config.add_path(canonical.clone())?;
-    eprintln!("\x1b[32m✓\x1b[0m Added \x1b[1m{}\x1b[0m to scan paths", canonical.display());
+    eprintln!(
+        "\x1b[32m✓\x1b[0m Added \x1b[1m{}\x1b[0m to scan paths",
+        canonical.display()
+    );
 
     // Scan the path immediately
     let mut db = Database::open()?;
     let mut scanner = Scanner::new(config, &mut db);
     eprintln!("\x1b[36m⏳\x1b[0m Scanning...");
     let result = scanner.scan_paths_only()?;
-    eprintln!("\x1b[32m✓\x1b[0m Found \x1b[1m{}\x1b[0m projects", result.from_paths);
+    eprintln!(
+        "\x1b[32m✓\x1b[0m Found \x1b[1m{}\x1b[0m projects",
+        result.from_paths
+    );
 
     Ok(())
 }
 
 fn remove_path(path: std::path::PathBuf, config: &mut Config) -> Result<()> {
     if config.remove_path(&path)? {
-        eprintln!("\x1b[32m✓\x1b[0m Removed \x1b[1m{}\x1b[0m from scan paths", path.display());
+        eprintln!(
+            "\x1b[32m✓\x1b[0m Removed \x1b[1m{}\x1b[0m from scan paths",
+            path.display()
+        );
     } else {
-        eprintln!("\x1b[33m⚠\x1b[0m Path \x1b[1m{}\x1b[0m was not in the scan list", path.display());
+        eprintln!(
+            "\x1b[33m⚠\x1b[0m Path \x1b[1m{}\x1b[0m was not in the scan list",
+            path.display()
+        );
     }
     Ok(())
 }
 
 fn show_config(config: &Config) -> Result<()> {
     eprintln!("\x1b[36mConfiguration\x1b[0m\n");
-    eprintln!("  \x1b[90mConfig file:\x1b[0m {}", Config::config_path()?.display());
-    eprintln!("  \x1b[90mDatabase:\x1b[0m    {}", Config::db_path()?.display());
+    eprintln!(
+        "  \x1b[90mConfig file:\x1b[0m {}",
+        Config::config_path()?.display()
+    );
+    eprintln!(
+        "  \x1b[90mDatabase:\x1b[0m    {}",
+        Config::db_path()?.display()
+    );
     eprintln!();
 
-    let spotlight_status = if config.use_spotlight { "\x1b[32m✓\x1b[0m" } else { "\x1b[31m✗\x1b[0m" };
-    eprintln!("  {} \x1b[1mSpotlight:\x1b[0m {}", spotlight_status, config.use_spotlight);
+    let spotlight_status = if config.use_spotlight {
+        "\x1b[32m✓\x1b[0m"
+    } else {
+        "\x1b[31m✗\x1b[0m"
+    };
+    eprintln!(
+        "  {} \x1b[1mSpotlight:\x1b[0m {}",
+        spotlight_status, config.use_spotlight
+    );
     eprintln!("  \x1b[90mSpotlight paths:\x1b[0m");
     for path in &config.spotlight_paths {
         eprintln!("    \x1b[90m•\x1b[0m {}", path.display());
@@ -672,8 +1223,38 @@ fn show_config(config: &Config) -> Result<()> {
     }
     eprintln!();
     eprintln!("  \x1b[90mMax depth:\x1b[0m    {}", config.max_depth);
-    eprintln!("  \x1b[90mPost command:\x1b[0m {}", config.post_command.as_deref().unwrap_or("\x1b[90m(none)\x1b[0m"));
+    eprintln!(
+        "  \x1b[90mPost command:\x1b[0m {}",
+        config
+            .post_command
+            .as_deref()
+            .unwrap_or("\x1b[90m(none)\x1b[0m")
+    );
+    eprintln!(
+        "  \x1b[90mFrecency weight:\x1b[0m {}",
+        config.frecency_weight
+    );
+    eprintln!(
+        "  \x1b[90mProximity weight:\x1b[0m {}",
+        config.proximity_weight
+    );
+    eprintln!("  \x1b[90mInteractive:\x1b[0m  {}", config.interactive);
+    #[cfg(feature = "semantic")]
+    {
+        let profile = embedding::profile();
+        let backend = match &profile.source {
+            #[cfg(feature = "semantic-local")]
+            embedding::ModelSource::Fastembed(_) => "fastembed".to_string(),
+            #[cfg(feature = "semantic-http")]
+            embedding::ModelSource::Http { url, .. } => url.clone(),
+        };
+        eprintln!(
+            "  \x1b[90mEmbedding model:\x1b[0m {} \x1b[90m({} dimensions, {})\x1b[0m",
+            profile.model_id, profile.dim, backend
+        );
+    }
+    #[cfg(not(feature = "semantic"))]
+    eprintln!("  \x1b[90mSemantic search:\x1b[0m not built in (full-text only)");
 
     Ok(())
 }
-
@@ -0,0 +1,520 @@
+use regex_lite::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// What a project says about itself in its build manifests
+#[derive(Debug, Default)]
+pub struct Manifest {
+    /// Package name, module path or artifact id (often differs from the directory)
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub keywords: Vec<String>,
+}
+
+impl Manifest {
+    /// Fill in what is still missing from another manifest (keywords are merged)
+    fn merge(&mut self, other: Manifest) {
+        if self.name.is_none() {
+            self.name = other.name;
+        }
+        if self.description.is_none() {
+            self.description = other.description;
+        }
+        for keyword in other.keywords {
+            if !self.keywords.contains(&keyword) {
+                self.keywords.push(keyword);
+            }
+        }
+    }
+}
+
+/// Read every manifest found at the root of a project. When several are
+/// present (a Rust crate with a package.json for its docs), earlier ones in
+/// this list win for name and description.
+pub fn read(path: &Path) -> Manifest {
+    let readers: &[fn(&Path) -> Option<Manifest>] = &[
+        cargo_toml,
+        package_json,
+        pyproject_toml,
+        go_mod,
+        pom_xml,
+        gradle,
+        composer_json,
+        mix_exs,
+        gemspec,
+        pubspec_yaml,
+        csproj,
+        deno_json,
+        flake_nix,
+    ];
+
+    let mut manifest = Manifest::default();
+    for reader in readers {
+        if let Some(found) = reader(path) {
+            manifest.merge(found);
+        }
+    }
+
+    manifest.name = clean(manifest.name);
+    manifest.description = clean(manifest.description);
+    manifest
+}
+
+/// Trim a value and drop it when empty
+fn clean(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// First file in the project root with the given extension (e.g. `gemspec`)
+fn file_with_extension(path: &Path, extension: &str) -> Option<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(path)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == extension))
+        .collect();
+    files.sort();
+    files.into_iter().next()
+}
+
+/// `[package]` (or `[workspace.package]`) in Cargo.toml
+fn cargo_toml(path: &Path) -> Option<Manifest> {
+    let value: toml::Value = fs::read_to_string(path.join("Cargo.toml"))
+        .ok()?
+        .parse()
+        .ok()?;
+    let package = value
+        .get("package")
+        .or_else(|| value.get("workspace")?.get("package"))?;
+    Some(toml_manifest(package))
+}
+
+/// `[project]` (PEP 621) or `[tool.poetry]` in pyproject.toml
+fn pyproject_toml(path: &Path) -> Option<Manifest> {
+    let value: toml::Value = fs::read_to_string(path.join("pyproject.toml"))
+        .ok()?
+        .parse()
+        .ok()?;
+    let project = value
+        .get("project")
+        .or_else(|| value.get("tool")?.get("poetry"))?;
+    Some(toml_manifest(project))
+}
+
+/// name, description and keywords of a TOML table
+fn toml_manifest(table: &toml::Value) -> Manifest {
+    let string = |key: &str| table.get(key).and_then(|v| v.as_str()).map(String::from);
+    Manifest {
+        name: string("name"),
+        description: string("description"),
+        keywords: table
+            .get("keywords")
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+fn package_json(path: &Path) -> Option<Manifest> {
+    json_manifest(&path.join("package.json"))
+}
+
+fn composer_json(path: &Path) -> Option<Manifest> {
+    json_manifest(&path.join("composer.json"))
+}
+
+fn deno_json(path: &Path) -> Option<Manifest> {
+    json_manifest(&path.join("deno.json"))
+}
+
+/// Top-level name, description and keywords of a JSON manifest
+fn json_manifest(file: &Path) -> Option<Manifest> {
+    let value: serde_json::Value = serde_json::from_str(&fs::read_to_string(file).ok()?).ok()?;
+    let string = |key: &str| value.get(key).and_then(|v| v.as_str()).map(String::from);
+    Some(Manifest {
+        name: string("name"),
+        description: string("description"),
+        keywords: value
+            .get("keywords")
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+/// `module github.com/acme/billing` in go.mod
+fn go_mod(path: &Path) -> Option<Manifest> {
+    let content = fs::read_to_string(path.join("go.mod")).ok()?;
+    let module = content.lines().find_map(|line| {
+        let line = line.split("//").next().unwrap_or("").trim();
+        line.strip_prefix("module")
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+    })?;
+    Some(Manifest {
+        name: Some(module.trim().trim_matches('"').to_string()),
+        ..Default::default()
+    })
+}
+
+/// `<name>` (or `<artifactId>`) and `<description>` of the project itself,
+/// not of its parent, dependencies or plugins
+fn pom_xml(path: &Path) -> Option<Manifest> {
+    let content = fs::read_to_string(path.join("pom.xml")).ok()?;
+    Some(Manifest {
+        name: xml_text(&content, &["name"]).or_else(|| xml_text(&content, &["artifactId"])),
+        description: xml_text(&content, &["description"]),
+        keywords: Vec::new(),
+    })
+}
+
+/// `<PackageId>`/`<AssemblyName>`, `<Description>` and `<PackageTags>` in a .csproj
+fn csproj(path: &Path) -> Option<Manifest> {
+    let content = fs::read_to_string(file_with_extension(path, "csproj")?).ok()?;
+    let property = |name: &str| xml_text(&content, &["PropertyGroup", name]);
+    Some(Manifest {
+        name: property("PackageId").or_else(|| property("AssemblyName")),
+        description: property("Description"),
+        keywords: property("PackageTags")
+            .map(|tags| {
+                tags.split([';', ',', ' '])
+                    .filter(|t| !t.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+/// `rootProject.name` in settings.gradle(.kts), `description` in build.gradle(.kts)
+fn gradle(path: &Path) -> Option<Manifest> {
+    let read = |names: [&str; 2]| {
+        names
+            .iter()
+            .find_map(|name| fs::read_to_string(path.join(name)).ok())
+    };
+    let build = read(["build.gradle.kts", "build.gradle"]);
+    let settings = read(["settings.gradle.kts", "settings.gradle"]);
+    if build.is_none() && settings.is_none() {
+        return None;
+    }
+
+    static NAME: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"(?m)^\s*rootProject\.name\s*=?\s*["']([^"']+)["']"#).unwrap()
+    });
+    static DESCRIPTION: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"(?m)^\s*description\s*=?\s*["']([^"']+)["']"#).unwrap());
+    Some(Manifest {
+        name: settings.and_then(|s| NAME.captures(&s).map(|c| c[1].to_string())),
+        description: build.and_then(|b| DESCRIPTION.captures(&b).map(|c| c[1].to_string())),
+        keywords: Vec::new(),
+    })
+}
+
+/// `app: :my_app` and `description: "..."` (or a `"""` heredoc) in mix.exs
+fn mix_exs(path: &Path) -> Option<Manifest> {
+    let content = fs::read_to_string(path.join("mix.exs")).ok()?;
+    static APP: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"\bapp:\s*:(\w+)"#).unwrap());
+    static HEREDOC: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"(?s)\bdescription:\s*"""(.*?)""""#).unwrap());
+    static STRING: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"\bdescription:\s*"((?:[^"\\]|\\.)*)""#).unwrap());
+    Some(Manifest {
+        name: APP.captures(&content).map(|c| c[1].to_string()),
+        description: HEREDOC
+            .captures(&content)
+            .or_else(|| STRING.captures(&content))
+            .map(|c| collapse_whitespace(&c[1].replace("\\\"", "\""))),
+        keywords: Vec::new(),
+    })
+}
+
+/// `spec.name`, `spec.description` (or `spec.summary`) in a *.gemspec
+fn gemspec(path: &Path) -> Option<Manifest> {
+    let content = fs::read_to_string(file_with_extension(path, "gemspec")?).ok()?;
+    static FIELD: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"(?m)^\s*\w+\.(name|description|summary)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+            .unwrap()
+    });
+    let field = |name: &str| {
+        FIELD
+            .captures_iter(&content)
+            .find(|c| &c[1] == name)
+            .and_then(|c| c.get(2).or_else(|| c.get(3)))
+            .map(|m| m.as_str().to_string())
+    };
+    Some(Manifest {
+        name: field("name"),
+        description: field("description").or_else(|| field("summary")),
+        keywords: Vec::new(),
+    })
+}
+
+/// `name`, `description` and `topics` in pubspec.yaml (Dart, Flutter)
+fn pubspec_yaml(path: &Path) -> Option<Manifest> {
+    let content = fs::read_to_string(path.join("pubspec.yaml")).ok()?;
+    Some(Manifest {
+        name: yaml_scalar(&content, "name"),
+        description: yaml_scalar(&content, "description"),
+        keywords: yaml_list(&content, "topics"),
+    })
+}
+
+/// `description = "...";` in flake.nix
+fn flake_nix(path: &Path) -> Option<Manifest> {
+    let content = fs::read_to_string(path.join("flake.nix")).ok()?;
+    static DESCRIPTION: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"\bdescription\s*=\s*"((?:[^"\\]|\\.)*)"\s*;"#).unwrap());
+    Some(Manifest {
+        description: DESCRIPTION
+            .captures(&content)
+            .map(|c| c[1].replace("\\\"", "\"")),
+        ..Default::default()
+    })
+}
+
+/// Text of the first element at `path` below the document root
+/// (`["PropertyGroup", "Description"]` in `<Project><PropertyGroup><Description>`)
+fn xml_text(xml: &str, path: &[&str]) -> Option<String> {
+    static COMMENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<!--.*?-->").unwrap());
+    static TAG: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"<(/?)([A-Za-z_][\w.:-]*)[^>]*?(/?)>").unwrap());
+    let xml = COMMENT.replace_all(xml, "");
+
+    // Open elements, the document root excluded
+    let mut stack: Vec<&str> = Vec::new();
+    let mut depth = 0;
+    let mut start = None;
+
+    for caps in TAG.captures_iter(&xml) {
+        let whole = caps.get(0).unwrap();
+        let name = caps.get(2).unwrap().as_str();
+        let closing = !caps[1].is_empty();
+        let self_closing = !caps[3].is_empty();
+
+        if closing {
+            if depth > 1 && stack.last() == Some(&name) {
+                if stack.as_slice() == path {
+                    if let Some(start) = start {
+                        return Some(xml_unescape(xml[start..whole.start()].trim()));
+                    }
+                }
+                stack.pop();
+            }
+            depth -= 1;
+        } else if !self_closing {
+            if depth > 0 {
+                stack.push(name);
+                if stack.as_slice() == path {
+                    start = Some(whole.end());
+                }
+            }
+            depth += 1;
+        }
+    }
+    None
+}
+
+fn xml_unescape(text: &str) -> String {
+    let text = text
+        .strip_prefix("<![CDATA[")
+        .and_then(|t| t.strip_suffix("]]>"))
+        .unwrap_or(text);
+    collapse_whitespace(
+        &text
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&"),
+    )
+}
+
+/// Value of a top-level `key:` in a YAML file: plain or quoted on the same
+/// line, or a `>`/`|` block on the indented lines below
+fn yaml_scalar(content: &str, key: &str) -> Option<String> {
+    let prefix = format!("{}:", key);
+    let mut lines = content.lines();
+    let value = lines.find_map(|line| line.strip_prefix(&prefix))?;
+    let value = value.split(" #").next().unwrap_or("").trim();
+
+    if value.is_empty() || value.starts_with('>') || value.starts_with('|') {
+        let block: Vec<&str> = lines
+            .take_while(|l| l.trim().is_empty() || l.starts_with(char::is_whitespace))
+            .map(str::trim)
+            .collect();
+        return Some(collapse_whitespace(&block.join(" ")));
+    }
+
+    Some(value.trim_matches(|c| c == '"' || c == '\'').to_string())
+}
+
+/// Items of a top-level `key:` list, `[a, b]` or one `- item` per line
+fn yaml_list(content: &str, key: &str) -> Vec<String> {
+    let prefix = format!("{}:", key);
+    let mut lines = content.lines();
+    let Some(value) = lines.find_map(|line| line.strip_prefix(&prefix)) else {
+        return Vec::new();
+    };
+    let unquote = |item: &str| {
+        item.trim()
+            .trim_matches(|c| c == '"' || c == '\'')
+            .to_string()
+    };
+
+    if let Some(inline) = value.trim().strip_prefix('[') {
+        return inline
+            .trim_end_matches(']')
+            .split(',')
+            .map(unquote)
+            .filter(|item| !item.is_empty())
+            .collect();
+    }
+
+    lines
+        .take_while(|l| l.trim().is_empty() || l.starts_with(char::is_whitespace))
+        .filter_map(|l| l.trim().strip_prefix('-'))
+        .map(unquote)
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Manifest read from a project holding the given files
+    fn read_files(files: &[(&str, &str)]) -> Manifest {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, content) in files {
+            fs::write(dir.path().join(name), content).unwrap();
+        }
+        read(dir.path())
+    }
+
+    #[test]
+    fn cargo_package_wins_over_package_json() {
+        let manifest = read_files(&[
+            ("Cargo.toml", "[package]\nname = \"foyer\"\ndescription = \"Hybrid cache\"\nkeywords = [\"cache\"]\n"),
+            ("package.json", r#"{"name": "foyer-docs", "description": "Docs site", "keywords": ["docs", "cache"]}"#),
+        ]);
+        assert_eq!(manifest.name.as_deref(), Some("foyer"));
+        assert_eq!(manifest.description.as_deref(), Some("Hybrid cache"));
+        assert_eq!(manifest.keywords, ["cache", "docs"]);
+    }
+
+    #[test]
+    fn go_module_path() {
+        let manifest = read_files(&[(
+            "go.mod",
+            "// billing\nmodule github.com/acme/billing\n\ngo 1.22\n",
+        )]);
+        assert_eq!(manifest.name.as_deref(), Some("github.com/acme/billing"));
+    }
+
+    #[test]
+    fn pom_ignores_parent_and_dependencies() {
+        let manifest = read_files(&[(
+            "pom.xml",
+            "<project>\n  <parent><artifactId>spring-parent</artifactId><description>Parent</description></parent>\n  \
+             <!-- <artifactId>commented</artifactId> -->\n  <artifactId>orders</artifactId>\n  \
+             <dependencies><dependency><artifactId>kafka</artifactId></dependency></dependencies>\n  \
+             <description>Order &amp; invoice\n    service</description>\n</project>\n",
+        )]);
+        assert_eq!(manifest.name.as_deref(), Some("orders"));
+        assert_eq!(
+            manifest.description.as_deref(),
+            Some("Order & invoice service")
+        );
+    }
+
+    #[test]
+    fn csproj_properties() {
+        let manifest = read_files(&[(
+            "Api.csproj",
+            "<Project Sdk=\"Microsoft.NET.Sdk\">\n  <PropertyGroup>\n    <AssemblyName>Acme.Api</AssemblyName>\n    \
+             <Description>Public API</Description>\n    <PackageTags>rest;grpc</PackageTags>\n  </PropertyGroup>\n</Project>\n",
+        )]);
+        assert_eq!(manifest.name.as_deref(), Some("Acme.Api"));
+        assert_eq!(manifest.description.as_deref(), Some("Public API"));
+        assert_eq!(manifest.keywords, ["rest", "grpc"]);
+    }
+
+    #[test]
+    fn gradle_settings_and_build() {
+        let manifest = read_files(&[
+            (
+                "settings.gradle.kts",
+                "rootProject.name = \"ledger\"\ninclude(\":core\")\n",
+            ),
+            (
+                "build.gradle",
+                "plugins { id 'java' }\ndescription 'Double-entry ledger'\n",
+            ),
+        ]);
+        assert_eq!(manifest.name.as_deref(), Some("ledger"));
+        assert_eq!(manifest.description.as_deref(), Some("Double-entry ledger"));
+    }
+
+    #[test]
+    fn mix_heredoc_description() {
+        let manifest = read_files(&[(
+            "mix.exs",
+            "def project do\n  [app: :chat_server,\n   description: \"\"\"\n   Realtime chat\n   backend\n   \"\"\"]\nend\n",
+        )]);
+        assert_eq!(manifest.name.as_deref(), Some("chat_server"));
+        assert_eq!(
+            manifest.description.as_deref(),
+            Some("Realtime chat backend")
+        );
+    }
+
+    #[test]
+    fn gemspec_falls_back_to_summary() {
+        let manifest = read_files(&[(
+            "rack_auth.gemspec",
+            "Gem::Specification.new do |spec|\n  spec.name = 'rack_auth'\n  spec.summary = \"Auth middleware\"\nend\n",
+        )]);
+        assert_eq!(manifest.name.as_deref(), Some("rack_auth"));
+        assert_eq!(manifest.description.as_deref(), Some("Auth middleware"));
+    }
+
+    #[test]
+    fn pubspec_block_description_and_topics() {
+        let manifest = read_files(&[(
+            "pubspec.yaml",
+            "name: weather_app\ndescription: >\n  Forecasts for\n  your city\ntopics:\n  - weather\n  - 'maps'\n",
+        )]);
+        assert_eq!(manifest.name.as_deref(), Some("weather_app"));
+        assert_eq!(
+            manifest.description.as_deref(),
+            Some("Forecasts for your city")
+        );
+        assert_eq!(manifest.keywords, ["weather", "maps"]);
+    }
+
+    #[test]
+    fn flake_description() {
+        let manifest = read_files(&[(
+            "flake.nix",
+            "{\n  description = \"A \\\"dev\\\" shell\";\n  outputs = { ... };\n}\n",
+        )]);
+        assert_eq!(manifest.description.as_deref(), Some("A \"dev\" shell"));
+    }
+}
@@ -0,0 +1,86 @@
+use anyhow::Result;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, IsTerminal, Write};
+
+use crate::git;
+use crate::ranking::ScoredProject;
+
+/// Outcome of asking the user to pick among close candidates
+pub enum Prompt {
+    /// Index of the chosen candidate
+    Picked(usize),
+    /// The user declined to choose
+    Cancelled,
+    /// No terminal to ask on (scripts, pipes)
+    Unavailable,
+}
+
+/// Ask on the terminal which candidate to open
+///
+/// Reads and writes /dev/tty directly: the shell function captures stdout
+/// and stderr, so the prompt has to bypass them to be seen.
+pub fn choose(query: &str, candidates: &[ScoredProject]) -> Result<Prompt> {
+    let Some(mut tty) = open_tty() else {
+        return Ok(Prompt::Unavailable);
+    };
+
+    let home = dirs::home_dir().unwrap_or_default();
+
+    writeln!(
+        tty,
+        "\x1b[33m?\x1b[0m Several projects match '\x1b[1m{}\x1b[0m':",
+        query
+    )?;
+    for (i, candidate) in candidates.iter().enumerate() {
+        let project = &candidate.project;
+        let path = match project.path.strip_prefix(&home) {
+            Ok(rest) => format!("~/{}", rest.display()),
+            Err(_) => project.path.display().to_string(),
+        };
+        let branch = git::status(&project.path)
+            .map(|(branch, dirty)| {
+                format!(" \x1b[33m{}{}\x1b[0m", branch, if dirty { "*" } else { "" })
+            })
+            .unwrap_or_default();
+
+        writeln!(
+            tty,
+            "  \x1b[33m{}.\x1b[0m \x1b[1m{}\x1b[0m{} \x1b[90m{} ({:.0}%)\x1b[0m",
+            i + 1,
+            project.display_name(),
+            branch,
+            path,
+            candidate.relevance
+        )?;
+    }
+    write!(
+        tty,
+        "Pick [1-{}, Enter = 1, q = cancel]: ",
+        candidates.len()
+    )?;
+    tty.flush()?;
+
+    let mut answer = String::new();
+    BufReader::new(tty.try_clone()?).read_line(&mut answer)?;
+    let answer = answer.trim();
+
+    if answer.is_empty() {
+        return Ok(Prompt::Picked(0));
+    }
+    match answer.parse::<usize>() {
+        Ok(n) if (1..=candidates.len()).contains(&n) => Ok(Prompt::Picked(n - 1)),
+        _ => Ok(Prompt::Cancelled),
+    }
+}
+
+/// The controlling terminal, when stdin is one (never in scripts or pipes)
+fn open_tty() -> Option<File> {
+    if !std::io::stdin().is_terminal() {
+        return None;
+    }
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()
+}
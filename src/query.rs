@@ -0,0 +1,445 @@
+use anyhow::{bail, Result};
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::db::{Database, Project, ProjectSource};
+use crate::{fuzzy, git, lexical};
+
+/// A structured filter parsed from an inline query operator
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    /// `lang:rust` - project tech stack contains this technology
+    Lang(String),
+    /// `under:~/work` - project path is inside this directory
+    Under(PathBuf),
+    /// `dirty:yes` / `dirty:no` - git working tree has uncommitted changes
+    Dirty(bool),
+    /// `src:spotlight` / `src:manual` / `src:scan` - how the project was discovered
+    Source(ProjectSource),
+    /// `accessed:<7d` / `accessed:>30d` / `accessed:never`
+    Accessed(AccessedFilter),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessedFilter {
+    Within(Duration),
+    OlderThan(Duration),
+    Never,
+}
+
+/// A search query split into free text and structured filters
+#[derive(Debug, Clone, Default)]
+pub struct ParsedQuery {
+    /// Text left after removing operators (sent to fuzzy/semantic search)
+    pub text: String,
+    pub filters: Vec<Filter>,
+    /// Folded words from `-word` / `!word`: projects whose name or path
+    /// below the scan root contain them are left out
+    pub excluded: Vec<String>,
+}
+
+/// Data loaded once per query for the filters that need more than the project row
+#[derive(Default)]
+struct FilterData<'a> {
+    tech_stacks: HashMap<PathBuf, Vec<String>>,
+    /// Configured scan roots (excluded words are matched below them)
+    scan_roots: &'a [PathBuf],
+    /// Description, keywords and tech stack of each indexed project, for exclusions
+    metadata: HashMap<PathBuf, String>,
+}
+
+/// Shortest word accepted after `-` or `!` as an exclusion. Shorter ones
+/// (`-c`, `-n`, stray flags typed after the query) are dropped, since a
+/// one- or two-letter prefix would exclude almost everything.
+const MIN_EXCLUDED_CHARS: usize = 3;
+
+/// Recognized operator keys (anything else containing ':' stays in the text)
+const OPERATORS: &[&str] = &[
+    "lang", "tech", "under", "in", "dirty", "src", "source", "accessed",
+];
+
+/// Common spellings of technologies, mapped to the names used by tech stack detection
+const LANG_ALIASES: &[(&str, &str)] = &[
+    ("rs", "rust"),
+    ("ts", "typescript"),
+    ("js", "javascript"),
+    ("node", "javascript"),
+    ("py", "python"),
+    ("golang", "go"),
+    ("rb", "ruby"),
+    ("kt", "kotlin"),
+    ("csharp", "c#"),
+    ("cs", "c#"),
+    ("fsharp", "f#"),
+    ("cpp", "c++"),
+    ("cxx", "c++"),
+    ("ex", "elixir"),
+    ("hs", "haskell"),
+    ("k8s", "kubernetes"),
+    ("tf", "terraform"),
+];
+
+impl ParsedQuery {
+    /// Parse inline operators out of a query
+    ///
+    /// `goto lang:rust under:~/work cache` searches for "cache" among Rust
+    /// projects under ~/work. Unknown `key:value` words are kept as text.
+    /// `-word` and `!word` (3+ characters) exclude projects named after
+    /// the word or inside a directory named after it.
+    pub fn parse(query: &str, cwd: Option<&Path>) -> Result<Self> {
+        let mut text_words = Vec::new();
+        let mut filters = Vec::new();
+        let mut excluded = Vec::new();
+
+        for word in query.split_whitespace() {
+            if let Some(term) = word.strip_prefix('-').or_else(|| word.strip_prefix('!')) {
+                let term = fuzzy::fold(term.trim_start_matches(['-', '!']));
+                if term.chars().count() >= MIN_EXCLUDED_CHARS {
+                    excluded.push(term);
+                }
+                continue;
+            }
+
+            let Some((key, value)) = word.split_once(':') else {
+                text_words.push(word);
+                continue;
+            };
+
+            let key = key.to_lowercase();
+            if !OPERATORS.contains(&key.as_str()) {
+                text_words.push(word);
+                continue;
+            }
+            if value.is_empty() {
+                bail!("Missing value for '{key}:' operator");
+            }
+
+            let filter = match key.as_str() {
+                "lang" | "tech" => Filter::Lang(normalize_lang(value)),
+                "under" | "in" => Filter::Under(expand_path(value, cwd)),
+                "dirty" => Filter::Dirty(parse_bool(value)?),
+                "src" | "source" => match value.parse::<ProjectSource>() {
+                    Ok(source) => Filter::Source(source),
+                    Err(e) => bail!("{e} (use spotlight, manual or scan)"),
+                },
+                _ => Filter::Accessed(parse_accessed(value)?),
+            };
+            filters.push(filter);
+        }
+
+        Ok(Self {
+            text: text_words.join(" "),
+            filters,
+            excluded,
+        })
+    }
+
+    pub fn has_filters(&self) -> bool {
+        !self.filters.is_empty() || !self.excluded.is_empty()
+    }
+
+    fn needs_tech_stack(&self) -> bool {
+        self.filters.iter().any(|f| matches!(f, Filter::Lang(_)))
+    }
+
+    /// Check whether an excluded word appears in the project name, in the
+    /// directories between the scan root (or home) and the project, or in its
+    /// indexed description, keywords and tech stack. The README is left out:
+    /// mentioning a word in passing isn't being it.
+    /// Words are compared with identifier-aware tokens, so `-old` leaves `golden` alone
+    /// but catches `old-api` and `OldClient`; `-archive` also catches `archived`.
+    fn is_excluded(&self, project: &Project, data: &FilterData) -> bool {
+        if self.excluded.is_empty() {
+            return false;
+        }
+
+        let root = data
+            .scan_roots
+            .iter()
+            .filter(|r| project.path.starts_with(r))
+            .max_by_key(|r| r.components().count())
+            .cloned()
+            .or_else(dirs::home_dir)
+            .unwrap_or_default();
+        let below_root = project.path.strip_prefix(&root).unwrap_or(&project.path);
+
+        let metadata = data
+            .metadata
+            .get(&project.path)
+            .map(String::as_str)
+            .unwrap_or("");
+        let tokens = lexical::tokenize(&format!(
+            "{} {} {}",
+            project.name,
+            below_root.to_string_lossy(),
+            metadata
+        ));
+        self.excluded
+            .iter()
+            .any(|term| tokens.iter().any(|token| token.starts_with(term.as_str())))
+    }
+
+    /// Check the filters that only need data already in the database
+    fn matches_static(&self, project: &Project, data: &FilterData) -> bool {
+        if self.is_excluded(project, data) {
+            return false;
+        }
+
+        self.filters.iter().all(|filter| match filter {
+            Filter::Lang(lang) => data
+                .tech_stacks
+                .get(&project.path)
+                .map(|stack| stack.iter().any(|t| t.to_lowercase() == *lang))
+                .unwrap_or(false),
+            Filter::Under(dir) => project.path.starts_with(dir),
+            Filter::Source(source) => project.source == *source,
+            Filter::Accessed(accessed) => {
+                let age = Utc::now() - project.last_accessed;
+                match accessed {
+                    AccessedFilter::Never => project.access_count == 0,
+                    AccessedFilter::Within(d) => project.access_count > 0 && age <= *d,
+                    AccessedFilter::OlderThan(d) => project.access_count == 0 || age > *d,
+                }
+            }
+            Filter::Dirty(_) => true,
+        })
+    }
+
+    /// Check the filters that need to run git (slow, so applied last)
+    fn matches_git(&self, project: &Project) -> bool {
+        self.filters.iter().all(|filter| match filter {
+            Filter::Dirty(wanted) => git::status(&project.path)
+                .map(|(_, dirty)| dirty == *wanted)
+                .unwrap_or(false),
+            _ => true,
+        })
+    }
+
+    /// Build a predicate for the cheap filters (loads tech stacks once if needed)
+    pub fn static_filter<'a>(
+        &'a self,
+        db: &Database,
+        scan_roots: &'a [PathBuf],
+    ) -> Result<impl Fn(&Project) -> bool + 'a> {
+        let mut data = FilterData {
+            scan_roots,
+            ..FilterData::default()
+        };
+        if self.needs_tech_stack() {
+            data.tech_stacks = db.get_tech_stacks()?;
+        }
+        if !self.excluded.is_empty() {
+            data.metadata = db.get_exclusion_metadata()?;
+        }
+        Ok(move |project: &Project| self.matches_static(project, &data))
+    }
+
+    /// Apply the git-based filters to an already narrowed-down candidate list
+    pub fn retain_git_matches<T>(&self, candidates: &mut Vec<T>, project: impl Fn(&T) -> &Project) {
+        if self.filters.iter().any(|f| matches!(f, Filter::Dirty(_))) {
+            candidates.retain(|c| self.matches_git(project(c)));
+        }
+    }
+}
+
+fn normalize_lang(value: &str) -> String {
+    let lower = value.to_lowercase();
+    LANG_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == lower)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or(lower)
+}
+
+/// Expand `~` and resolve relative paths against the current directory
+fn expand_path(value: &str, cwd: Option<&Path>) -> PathBuf {
+    let path = if value == "~" {
+        dirs::home_dir().unwrap_or_default()
+    } else if let Some(rest) = value.strip_prefix("~/") {
+        dirs::home_dir().unwrap_or_default().join(rest)
+    } else {
+        PathBuf::from(value)
+    };
+
+    let absolute = match cwd {
+        Some(cwd) if path.is_relative() => cwd.join(path),
+        _ => path,
+    };
+    absolute.canonicalize().unwrap_or(absolute)
+}
+
+fn parse_bool(value: &str) -> Result<bool> {
+    match value.to_lowercase().as_str() {
+        "yes" | "y" | "true" | "1" => Ok(true),
+        "no" | "n" | "false" | "0" => Ok(false),
+        _ => bail!("Invalid value '{value}' for dirty: (use yes or no)"),
+    }
+}
+
+/// Parse `<7d`, `>2w`, `12h`, `never` (a bare duration means "within")
+fn parse_accessed(value: &str) -> Result<AccessedFilter> {
+    if value.eq_ignore_ascii_case("never") {
+        return Ok(AccessedFilter::Never);
+    }
+
+    let (older, spec) = if let Some(rest) = value.strip_prefix('>') {
+        (true, rest)
+    } else {
+        (false, value.strip_prefix('<').unwrap_or(value))
+    };
+
+    let split = spec
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(spec.len());
+    let (amount, unit) = spec.split_at(split);
+    let amount: i64 = match amount.parse() {
+        Ok(n) => n,
+        Err(_) => bail!("Invalid duration '{value}' for accessed: (e.g. <7d, >2w, 12h, never)"),
+    };
+
+    let duration = match unit {
+        "h" => Duration::try_hours(amount),
+        "d" | "" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        "m" => amount.checked_mul(30).and_then(Duration::try_days),
+        "y" => amount.checked_mul(365).and_then(Duration::try_days),
+        _ => bail!("Unknown unit '{unit}' for accessed: (use h, d, w, m or y)"),
+    };
+    let Some(duration) = duration else {
+        bail!("Duration '{value}' for accessed: is too large");
+    };
+
+    Ok(if older {
+        AccessedFilter::OlderThan(duration)
+    } else {
+        AccessedFilter::Within(duration)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_splits_operators_from_text() {
+        let parsed = ParsedQuery::parse("lang:rs cache dirty:no src:manual", None).unwrap();
+        assert_eq!(parsed.text, "cache");
+        assert_eq!(
+            parsed.filters,
+            vec![
+                Filter::Lang("rust".into()),
+                Filter::Dirty(false),
+                Filter::Source(ProjectSource::Manual)
+            ]
+        );
+        assert!(parsed.has_filters());
+    }
+
+    #[test]
+    fn parse_keeps_unknown_keys_as_text() {
+        let parsed = ParsedQuery::parse("http://host TODO: api", None).unwrap();
+        assert_eq!(parsed.text, "http://host TODO: api");
+        assert!(!parsed.has_filters());
+    }
+
+    #[test]
+    fn parse_resolves_under_against_cwd() {
+        let parsed =
+            ParsedQuery::parse("under:clients api", Some(Path::new("/nonexistent/work"))).unwrap();
+        assert_eq!(
+            parsed.filters,
+            vec![Filter::Under(PathBuf::from("/nonexistent/work/clients"))]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_bad_values() {
+        assert!(ParsedQuery::parse("lang:", None).is_err());
+        assert!(ParsedQuery::parse("dirty:maybe", None).is_err());
+        assert!(ParsedQuery::parse("src:github", None).is_err());
+        assert!(ParsedQuery::parse("accessed:soon", None).is_err());
+    }
+
+    #[test]
+    fn parse_collects_exclusions_of_three_chars_or_more() {
+        let parsed = ParsedQuery::parse("kafka -Légacy !old --archive -c -ab !", None).unwrap();
+        assert_eq!(parsed.text, "kafka");
+        assert_eq!(parsed.excluded, ["legacy", "old", "archive"]);
+        assert!(parsed.has_filters());
+    }
+
+    #[test]
+    fn exclusions_match_name_and_directories_below_the_scan_root() {
+        let roots = [PathBuf::from("/code")];
+        let parsed = ParsedQuery::parse("api -old -archive -code", None).unwrap();
+        let data = FilterData {
+            scan_roots: &roots,
+            ..FilterData::default()
+        };
+        let excluded = |path: &str| parsed.is_excluded(&Project::for_test(path), &data);
+
+        assert!(excluded("/code/old-api"));
+        assert!(excluded("/code/OldClient"));
+        assert!(excluded("/code/archived/api"));
+        assert!(!excluded("/code/golden-api"));
+        // The scan root itself never excludes anything
+        assert!(!excluded("/code/web/api"));
+    }
+
+    #[test]
+    fn exclusions_outside_scan_roots_use_the_full_path_or_home() {
+        let parsed = ParsedQuery::parse("api -legacy", None).unwrap();
+        let data = FilterData::default();
+        assert!(parsed.is_excluded(&Project::for_test("/srv/legacy/api"), &data));
+        assert!(!parsed.is_excluded(&Project::for_test("/srv/current/api"), &data));
+    }
+
+    #[test]
+    fn exclusions_match_indexed_metadata() {
+        let roots = [PathBuf::from("/code")];
+        let mut data = FilterData {
+            scan_roots: &roots,
+            ..FilterData::default()
+        };
+        data.metadata.insert(
+            PathBuf::from("/code/billing"),
+            "Deprecated billing service java spring".to_string(),
+        );
+        data.metadata
+            .insert(PathBuf::from("/code/ledger"), "Ledger kotlin".to_string());
+
+        let parsed = ParsedQuery::parse("api -deprecated -java", None).unwrap();
+        assert!(parsed.is_excluded(&Project::for_test("/code/billing"), &data));
+        assert!(!parsed.is_excluded(&Project::for_test("/code/ledger"), &data));
+    }
+
+    #[test]
+    fn parse_accessed_durations() {
+        assert_eq!(parse_accessed("never").unwrap(), AccessedFilter::Never);
+        assert_eq!(
+            parse_accessed("<7d").unwrap(),
+            AccessedFilter::Within(Duration::days(7))
+        );
+        assert_eq!(
+            parse_accessed("7").unwrap(),
+            AccessedFilter::Within(Duration::days(7))
+        );
+        assert_eq!(
+            parse_accessed(">2w").unwrap(),
+            AccessedFilter::OlderThan(Duration::weeks(2))
+        );
+        assert_eq!(
+            parse_accessed("12h").unwrap(),
+            AccessedFilter::Within(Duration::hours(12))
+        );
+        assert_eq!(
+            parse_accessed(">1y").unwrap(),
+            AccessedFilter::OlderThan(Duration::days(365))
+        );
+        assert!(parse_accessed("<d").is_err());
+        assert!(parse_accessed("3x").is_err());
+        assert!(parse_accessed("<99999999999y").is_err());
+        assert!(parse_accessed(">99999999999999h").is_err());
+    }
+}
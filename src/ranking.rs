@@ -0,0 +1,659 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::db::{Database, Project};
+use crate::fuzzy;
+use crate::learning::{self, BoostWeights};
+use crate::lexical::{self, ExpandedQuery};
+use crate::query::ParsedQuery;
+#[cfg(feature = "semantic")]
+use crate::semantic;
+
+/// Minimum relevance to accept a match (below this = no match)
+/// Semantic scores are calibrated, so 55 sits just above the 50% midpoint
+pub const SEMANTIC_MIN_THRESHOLD: f64 = 55.0;
+
+/// Boost score if project name contains query
+const SUBSTRING_BOOST: f32 = 20.0;
+
+/// Stronger boost if project name exactly matches query
+const EXACT_NAME_BOOST: f32 = 40.0;
+
+/// Smaller boost if query words found in metadata (README, folders, types)
+const METADATA_BOOST: f32 = 10.0;
+
+/// Maximum boost given by a fuzzy name match (scaled by the fuzzy score)
+const FUZZY_BOOST: f32 = 20.0;
+
+/// Weight of the fuzzy score when a project has no semantic score
+const FUZZY_ONLY_WEIGHT: f32 = 0.8;
+
+/// Fuzzy scores below this are ignored when collecting candidates
+const FUZZY_MIN_SCORE: f32 = 40.0;
+
+/// Fuzzy score high enough to jump without loading the embedding model
+const FUZZY_CONFIDENT_SCORE: f32 = 70.0;
+
+/// Required lead of the best fuzzy match over the runner-up to skip semantic search
+const FUZZY_CONFIDENT_MARGIN: f32 = 10.0;
+
+/// How many more candidates to retrieve when inline filters are active
+const FILTERED_FETCH_MULTIPLIER: usize = 5;
+
+/// Frecency score at which half of the configured frecency weight is granted
+const FRECENCY_HALF_SATURATION: f64 = 100.0;
+
+/// Share of the proximity weight for projects under the same scan root only
+const SAME_ROOT_SHARE: f32 = 0.25;
+
+/// Share of the proximity weight kept per extra level between the deepest
+/// directory shared with the cwd and the project (cousins, nested members)
+const SHARED_PREFIX_SHARE: f32 = 0.75;
+
+/// A project with its individual and combined ranking scores
+#[derive(Debug, Clone)]
+pub struct ScoredProject {
+    pub project: Project,
+    /// Hybrid retrieval score (vector + full-text, fused with RRF)
+    pub semantic: Option<f32>,
+    pub fuzzy: f32,
+    /// Name/metadata rule that boosted this candidate
+    pub boost: NameBoost,
+    /// Relevance points from past selections for the same query
+    pub learned: f32,
+    /// How well the project matches the query (0-100, compared to the threshold)
+    pub relevance: f32,
+    /// Points added for frequent and recent use
+    pub frecency: f32,
+    /// Points added for being close to the current directory
+    pub proximity: f32,
+    /// Final ranking score (relevance + frecency + proximity)
+    pub score: f32,
+}
+
+/// Knobs for a single ranking pass
+#[derive(Debug, Clone, Copy)]
+pub struct RankOptions<'a> {
+    /// Number of candidates to retrieve from the indexes
+    pub limit: usize,
+    /// Query the vector and full-text indexes
+    pub use_semantic: bool,
+    /// Skip the indexes when the fuzzy winner is unambiguous
+    pub allow_fuzzy_shortcut: bool,
+    /// Directory the search is run from (for proximity ranking)
+    pub cwd: Option<&'a Path>,
+}
+
+impl ScoredProject {
+    /// Short label describing which signal drove the match
+    pub fn match_kind(&self) -> &'static str {
+        match self.semantic {
+            Some(s) if s >= self.fuzzy * FUZZY_ONLY_WEIGHT => {
+                if cfg!(feature = "semantic") {
+                    "semantic"
+                } else {
+                    "full-text"
+                }
+            }
+            _ => "fuzzy",
+        }
+    }
+}
+
+/// Which name/metadata rule boosted a candidate (the learnable part of the ranking)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameBoost {
+    ExactName,
+    Substring,
+    Metadata,
+    None,
+}
+
+/// Calculate boosted score based on name, fuzzy and metadata matching
+///
+/// Boost constants are scaled by `weights`, learned from past selections.
+/// Synonyms from the user's dictionary count as the word they expand
+/// (`gw` matches a `gateway` project name).
+pub fn calculate_boosted_score(
+    project_name: &str,
+    query: &ExpandedQuery,
+    semantic_score: Option<f32>,
+    fuzzy_score: f32,
+    embedded_text: Option<&str>,
+    weights: &BoostWeights,
+) -> (f32, NameBoost) {
+    let name_lower = fuzzy::fold(project_name);
+    let base_score = semantic_score
+        .unwrap_or(0.0)
+        .max(fuzzy_score * FUZZY_ONLY_WEIGHT);
+    let whole_alternatives = query.whole_alternatives();
+
+    // Check for exact match first (strongest boost)
+    if name_lower == query.text || whole_alternatives.iter().any(|a| name_lower == *a) {
+        let boost = EXACT_NAME_BOOST * weights.exact_name;
+        return ((base_score + boost).min(100.0), NameBoost::ExactName);
+    }
+
+    // Check if name contains the full query
+    let substring_boost = SUBSTRING_BOOST * weights.substring;
+    if name_lower.contains(&query.text)
+        || whole_alternatives.iter().any(|a| name_lower.contains(*a))
+    {
+        return (
+            (base_score + substring_boost).min(100.0),
+            NameBoost::Substring,
+        );
+    }
+
+    // Check if name contains ALL significant words from the query
+    // (3+ chars, or shorter words with synonyms), each through any alternative
+    let query_words: Vec<&Vec<String>> = query
+        .words
+        .iter()
+        .filter(|alternatives| alternatives[0].len() >= 3 || alternatives.len() > 1)
+        .collect();
+    let contains_word = |haystack: &str, alternatives: &[String]| {
+        alternatives
+            .iter()
+            .any(|a| a.split_whitespace().all(|w| haystack.contains(w)))
+    };
+
+    if !query_words.is_empty() {
+        let all_words_match = query_words.iter().all(|w| contains_word(&name_lower, w));
+        if all_words_match {
+            return (
+                (base_score + substring_boost).min(100.0),
+                NameBoost::Substring,
+            );
+        }
+    }
+
+    // Fragments and initialisms of the name (e.g. "gcp" → goto-cli-project)
+    let fuzzy_boost = FUZZY_BOOST * fuzzy_score / 100.0;
+
+    // Check if ALL query words appear in embedded metadata
+    if !query_words.is_empty() {
+        if let Some(text) = embedded_text {
+            let text_lower = fuzzy::fold(text);
+            let all_in_metadata = query_words.iter().all(|w| contains_word(&text_lower, w));
+            if all_in_metadata {
+                let boost = (METADATA_BOOST * weights.metadata).max(fuzzy_boost);
+                return ((base_score + boost).min(100.0), NameBoost::Metadata);
+            }
+        }
+    }
+
+    ((base_score + fuzzy_boost).min(100.0), NameBoost::None)
+}
+
+/// Frecency bonus in points, saturating towards `weight` for heavily used projects
+pub fn frecency_boost(project: &Project, weight: f32) -> f32 {
+    if weight <= 0.0 || project.access_count == 0 {
+        return 0.0;
+    }
+    let frecency = project.frecency_score();
+    (weight as f64 * frecency / (frecency + FRECENCY_HALF_SATURATION)) as f32
+}
+
+/// Proximity bonus in points for a project close to the current directory
+///
+/// Scored by the deepest directory the project shares with the cwd. When it
+/// lies below the scan root, siblings (projects right under it) get the full
+/// weight and each extra level down to the project costs a share. Projects
+/// that only share the scan root itself get a small share, however close
+/// to the root they sit.
+pub fn proximity_boost(
+    cwd: &Path,
+    project_path: &Path,
+    scan_roots: &[PathBuf],
+    weight: f32,
+) -> f32 {
+    if weight <= 0.0 {
+        return 0.0;
+    }
+    // Deepest scan root containing both paths, falling back to the home directory
+    let scan_root = scan_roots
+        .iter()
+        .filter(|r| cwd.starts_with(r) && project_path.starts_with(r))
+        .max_by_key(|r| r.components().count());
+    let root_depth = match scan_root {
+        Some(root) => root.components().count(),
+        None => match dirs::home_dir() {
+            Some(home) if cwd.starts_with(&home) && project_path.starts_with(&home) => {
+                home.components().count()
+            }
+            _ => return 0.0,
+        },
+    };
+
+    let shared = cwd
+        .components()
+        .zip(project_path.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if shared <= root_depth {
+        return if scan_root.is_some() {
+            weight * SAME_ROOT_SHARE
+        } else {
+            0.0
+        };
+    }
+
+    // Levels from the shared directory down to the project: 0 when the cwd is
+    // inside it, 1 for a sibling
+    let down = project_path.components().count().saturating_sub(shared);
+    if down <= 1 {
+        weight
+    } else {
+        (weight * SHARED_PREFIX_SHARE.powi(down as i32 - 1)).max(weight * SAME_ROOT_SHARE)
+    }
+}
+
+/// Path of the project the current directory is in
+///
+/// With nested projects (a workspace and its members) only the deepest one
+/// containing the cwd counts, so from inside a member the root is still a
+/// valid target.
+pub fn current_project<'a>(cwd: Option<&Path>, projects: &'a [Project]) -> Option<&'a Path> {
+    let cwd = cwd?;
+    projects
+        .iter()
+        .map(|p| p.path.as_path())
+        .filter(|path| cwd.starts_with(path))
+        .max_by_key(|path| path.components().count())
+}
+
+/// Rank projects for a query by fusing fuzzy name matching with semantic search
+///
+/// Fuzzy scoring runs over every project. Semantic search only runs when
+/// `use_semantic` is set, and is skipped when the fuzzy winner is unambiguous
+/// and `allow_fuzzy_shortcut` is set, so the embedding model is never loaded.
+/// Frecency and proximity to the current directory are added on top of
+/// relevance to break near-ties.
+pub fn rank_projects(
+    parsed: &ParsedQuery,
+    projects: &[Project],
+    db: &Database,
+    config: &Config,
+    options: RankOptions,
+) -> Result<Vec<ScoredProject>> {
+    let RankOptions {
+        limit,
+        use_semantic,
+        allow_fuzzy_shortcut,
+        cwd,
+    } = options;
+    let query = parsed.text.as_str();
+    let expanded = ExpandedQuery::new(query, &config.synonyms);
+
+    // Inline operators (lang:, under:, src:, accessed:) narrow the candidates
+    let passes_filters = parsed.static_filter(db, &config.scan_paths)?;
+    let projects: Vec<&Project> = projects.iter().filter(|p| passes_filters(p)).collect();
+
+    // Only operators, no text: every project passing the filters is a full match
+    let filter_only = query.trim().is_empty();
+
+    // Fuzzy candidates from the full project list
+    let mut fuzzy_hits: Vec<(&Project, f32)> = projects
+        .iter()
+        .map(|p| (*p, fuzzy::score_project(query, &p.name, &p.path)))
+        .filter(|(_, s)| filter_only || *s >= FUZZY_MIN_SCORE)
+        .collect();
+    fuzzy_hits.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    if !filter_only {
+        fuzzy_hits.truncate(limit.max(10));
+    }
+
+    let fuzzy_is_confident = match fuzzy_hits.as_slice() {
+        [(_, best), (_, second), ..] => {
+            *best >= FUZZY_CONFIDENT_SCORE && best - second >= FUZZY_CONFIDENT_MARGIN
+        }
+        [(_, best)] => *best >= FUZZY_CONFIDENT_SCORE,
+        [] => false,
+    };
+
+    let mut candidates: HashMap<PathBuf, (Project, Option<f32>, f32)> = fuzzy_hits
+        .into_iter()
+        .map(|(p, s)| (p.path.clone(), (p.clone(), None, s)))
+        .collect();
+
+    let run_semantic =
+        use_semantic && !filter_only && !(allow_fuzzy_shortcut && fuzzy_is_confident);
+    if run_semantic {
+        // Filters discard retrieved hits, so fetch deeper to keep enough candidates
+        let fetch_limit = if parsed.has_filters() {
+            limit * FILTERED_FETCH_MULTIPLIER
+        } else {
+            limit
+        };
+        for (project, score) in hybrid_retrieve(db, &expanded, fetch_limit)? {
+            if !passes_filters(&project) {
+                continue;
+            }
+            candidates
+                .entry(project.path.clone())
+                .and_modify(|c| c.1 = Some(score))
+                .or_insert_with(|| {
+                    let fuzzy_score = fuzzy::score_project(query, &project.name, &project.path);
+                    (project, Some(score), fuzzy_score)
+                });
+        }
+    }
+
+    // Projects previously chosen for this query are candidates even if retrieval missed them
+    let preferences = learning::query_preferences(db, query)?;
+    for project in projects
+        .iter()
+        .filter(|p| preferences.get(&p.path).is_some_and(|&l| l > 0.0))
+    {
+        candidates.entry(project.path.clone()).or_insert_with(|| {
+            let fuzzy_score = fuzzy::score_project(query, &project.name, &project.path);
+            ((*project).clone(), None, fuzzy_score)
+        });
+    }
+
+    let weights = learning::load_weights(db)?;
+
+    let mut ranked: Vec<ScoredProject> = candidates
+        .into_values()
+        .map(|(project, semantic_score, fuzzy_score)| {
+            let (relevance, boost) = if filter_only {
+                (100.0, NameBoost::None)
+            } else {
+                let embedded_text = db.get_embedded_text(&project.path).ok().flatten();
+                calculate_boosted_score(
+                    &project.name,
+                    &expanded,
+                    semantic_score,
+                    fuzzy_score,
+                    embedded_text.as_deref(),
+                    &weights,
+                )
+            };
+            let learned = preferences.get(&project.path).copied().unwrap_or(0.0);
+            let relevance = (relevance + learned).clamp(0.0, 100.0);
+            let frecency = frecency_boost(&project, config.frecency_weight);
+            let proximity = cwd
+                .map(|c| {
+                    proximity_boost(
+                        c,
+                        &project.path,
+                        &config.scan_paths,
+                        config.proximity_weight,
+                    )
+                })
+                .unwrap_or(0.0);
+            ScoredProject {
+                project,
+                semantic: semantic_score,
+                fuzzy: fuzzy_score,
+                boost,
+                learned,
+                relevance,
+                frecency,
+                proximity,
+                score: relevance + frecency + proximity,
+            }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    parsed.retain_git_matches(&mut ranked, |c| &c.project);
+    Ok(ranked)
+}
+
+/// Nearest projects in the vector index (none until projects are indexed)
+#[cfg(feature = "semantic")]
+fn semantic_hits(db: &Database, query: &str, limit: usize) -> Result<Vec<(Project, f32)>> {
+    let (indexed, _) = db.embedding_stats()?;
+    if indexed == 0 {
+        return Ok(Vec::new());
+    }
+    match semantic::semantic_search(db, query, limit) {
+        Ok(hits) => Ok(hits),
+        Err(e) => {
+            // Names and the full-text index still work: warn once and carry on
+            static WARNED: std::sync::Once = std::sync::Once::new();
+            WARNED.call_once(|| {
+                eprintln!("\x1b[33m⚠\x1b[0m Semantic search failed, ranking by name and full text only: {:#}", e);
+            });
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Builds without semantic search rank with the full-text index alone
+#[cfg(not(feature = "semantic"))]
+fn semantic_hits(_db: &Database, _query: &str, _limit: usize) -> Result<Vec<(Project, f32)>> {
+    Ok(Vec::new())
+}
+
+/// Retrieve candidates from the vector and full-text indexes, merged with RRF
+///
+/// RRF only decides which candidates make the cut and in what order. Each
+/// keeps its own calibrated score: the better of its semantic similarity
+/// and its full-text match quality, so a weak README mention found by one
+/// index isn't lifted by how well other hits did.
+fn hybrid_retrieve(
+    db: &Database,
+    query: &ExpandedQuery,
+    limit: usize,
+) -> Result<Vec<(Project, f32)>> {
+    let search_text = query.search_text();
+    let semantic_hits = semantic_hits(db, &search_text, limit)?;
+
+    // Scored against the query and each whole-query synonym, whichever matches best
+    let phrasings: Vec<&str> = std::iter::once(query.original.as_str())
+        .chain(query.whole_alternatives())
+        .collect();
+    let lexical_hits: Vec<(Project, f32)> = match lexical::match_expression(&search_text) {
+        Some(expr) => db
+            .find_lexical(&expr, limit)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(id, columns)| {
+                let score = phrasings
+                    .iter()
+                    .map(|q| lexical::hit_score(q, &columns))
+                    .fold(0.0, f32::max);
+                Some((db.get_project_by_id(id).ok().flatten()?, score))
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let fused = lexical::reciprocal_rank_fusion(&[
+        semantic_hits.iter().map(|(p, _)| p.path.clone()).collect(),
+        lexical_hits.iter().map(|(p, _)| p.path.clone()).collect(),
+    ]);
+
+    let mut merged: HashMap<PathBuf, (Project, f32)> = HashMap::new();
+    for (project, score) in semantic_hits.into_iter().chain(lexical_hits) {
+        let entry = merged.entry(project.path.clone()).or_insert((project, 0.0));
+        entry.1 = entry.1.max(score);
+    }
+
+    let mut hits: Vec<(Project, f32)> = merged.into_values().collect();
+    hits.sort_by(|a, b| {
+        let rank = |p: &Project| fused.get(&p.path).copied().unwrap_or(0.0);
+        rank(&b.0)
+            .partial_cmp(&rank(&a.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    hits.truncate(limit);
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use std::collections::BTreeMap;
+
+    /// Project visited `count` times, last `hours_ago`
+    fn visited(count: i64, hours_ago: i64) -> Project {
+        Project {
+            access_count: count,
+            last_accessed: Utc::now() - Duration::hours(hours_ago),
+            ..Project::for_test("/code/billing-api")
+        }
+    }
+
+    fn boosted(name: &str, query: &str, semantic: Option<f32>, fuzzy: f32) -> (f32, NameBoost) {
+        let query = ExpandedQuery::new(query, &BTreeMap::new());
+        calculate_boosted_score(
+            name,
+            &query,
+            semantic,
+            fuzzy,
+            None,
+            &BoostWeights::default(),
+        )
+    }
+
+    #[test]
+    fn current_project_is_the_deepest_containing_the_cwd() {
+        let projects = [
+            Project::for_test("/code/shop"),
+            Project::for_test("/code/shop/crates/api"),
+            Project::for_test("/code/shop-web"),
+        ];
+        let current = |cwd: &str| current_project(Some(Path::new(cwd)), &projects);
+
+        assert_eq!(
+            current("/code/shop/crates/api/src"),
+            Some(Path::new("/code/shop/crates/api"))
+        );
+        assert_eq!(current("/code/shop/docs"), Some(Path::new("/code/shop")));
+        assert_eq!(current("/code/shop-web"), Some(Path::new("/code/shop-web")));
+        assert_eq!(current("/code"), None);
+        assert_eq!(current_project(None, &projects), None);
+    }
+
+    /// Proximity bonus from /code/clientA/api/src, with /code as the scan root
+    #[test]
+    fn frecency_halves_every_three_days_without_visits() {
+        let fresh = visited(10, 0).frecency_score();
+        assert!((visited(10, 72).frecency_score() - fresh / 2.0).abs() < 1e-9);
+        assert!((visited(10, 144).frecency_score() - fresh / 4.0).abs() < 1e-9);
+
+        let boosts: Vec<f32> = [0, 24, 72, 720]
+            .iter()
+            .map(|&hours| frecency_boost(&visited(10, hours), 10.0))
+            .collect();
+        assert!(boosts.windows(2).all(|w| w[0] > w[1]), "{boosts:?}");
+    }
+
+    #[test]
+    fn frecency_boost_saturates_below_the_weight() {
+        assert_eq!(frecency_boost(&visited(0, 0), 10.0), 0.0);
+        assert_eq!(frecency_boost(&visited(50, 0), 0.0), 0.0);
+
+        let heavy = frecency_boost(&visited(1_000_000, 0), 10.0);
+        assert!(heavy > 5.0 && heavy < 10.0, "{heavy}");
+        // Half the weight is granted at FRECENCY_HALF_SATURATION
+        let project = visited(3, 0);
+        let frecency = project.frecency_score();
+        let expected = 10.0 * frecency / (frecency + FRECENCY_HALF_SATURATION);
+        assert!((frecency_boost(&project, 10.0) as f64 - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    fn recent_and_frequent_visits_earn_more_frecency() {
+        let boost = |count, hours_ago| frecency_boost(&visited(count, hours_ago), 10.0);
+        assert!(boost(20, 2) > boost(20, 24 * 30), "recent beats stale");
+        assert!(boost(20, 2) > boost(2, 2), "frequent beats rare");
+        assert!(
+            boost(2, 2) > boost(20, 24 * 30),
+            "a recent visit outweighs an old habit"
+        );
+    }
+
+    #[test]
+    fn name_boosts_add_to_the_best_base_score_up_to_100() {
+        // Base score is the semantic score or the discounted fuzzy score, whichever is higher
+        assert_eq!(
+            boosted("ledger", "billing", Some(60.0), 0.0),
+            (60.0, NameBoost::None)
+        );
+        // plus a share of FUZZY_BOOST for a partial name match
+        let (fuzzy_only, _) = boosted("ledger", "billing", Some(30.0), 50.0);
+        assert_eq!(fuzzy_only, 50.0 * FUZZY_ONLY_WEIGHT + FUZZY_BOOST / 2.0);
+
+        let (substring, boost) = boosted("billing-api", "billing", Some(50.0), 0.0);
+        assert_eq!(
+            (substring, boost),
+            (50.0 + SUBSTRING_BOOST, NameBoost::Substring)
+        );
+        let (exact, boost) = boosted("billing", "billing", Some(50.0), 0.0);
+        assert_eq!(
+            (exact, boost),
+            (50.0 + EXACT_NAME_BOOST, NameBoost::ExactName)
+        );
+        assert_eq!(
+            boosted("billing", "billing", Some(90.0), 0.0),
+            (100.0, NameBoost::ExactName)
+        );
+    }
+
+    #[test]
+    fn frecency_breaks_ties_between_equally_relevant_projects() {
+        let (relevance, _) = boosted("billing-api", "billing", Some(60.0), 0.0);
+        let daily = relevance + frecency_boost(&visited(30, 4), 10.0);
+        let forgotten = relevance + frecency_boost(&visited(1, 24 * 60), 10.0);
+        assert!(daily > forgotten);
+        assert!(
+            daily - relevance < 10.0,
+            "frecency never outweighs its configured weight"
+        );
+    }
+
+    fn nearby(project_path: &str) -> f32 {
+        let roots = [PathBuf::from("/code")];
+        proximity_boost(
+            Path::new("/code/clientA/api/src"),
+            Path::new(project_path),
+            &roots,
+            8.0,
+        )
+    }
+
+    #[test]
+    fn proximity_favours_siblings() {
+        assert_eq!(nearby("/code/clientA/api"), 8.0);
+        assert_eq!(nearby("/code/clientA/web"), 8.0);
+    }
+
+    #[test]
+    fn proximity_of_cousins_decreases_with_depth() {
+        let nested = nearby("/code/clientA/libs/core");
+        assert!(
+            nested < 8.0 && nested > nearby("/code/billing-api"),
+            "{nested}"
+        );
+        assert!(nearby("/code/clientA/libs/rust/core") < nested);
+    }
+
+    #[test]
+    fn projects_sharing_only_the_scan_root_get_a_small_share() {
+        let root_level = nearby("/code/billing-api");
+        assert_eq!(root_level, 8.0 * SAME_ROOT_SHARE);
+        assert_eq!(nearby("/code/clientB/api"), root_level);
+        let roots = [PathBuf::from("/code")];
+        assert_eq!(
+            proximity_boost(
+                Path::new("/code/clientA/api"),
+                Path::new("/work/api"),
+                &roots,
+                8.0
+            ),
+            0.0
+        );
+    }
+}
@@ -1,10 +1,12 @@
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use walkdir::WalkDir;
 
 use crate::config::Config;
 use crate::db::{Database, ProjectSource};
+use crate::walk;
+use crate::workspace;
 
 pub struct Scanner<'a> {
     config: &'a Config,
@@ -52,39 +54,19 @@ impl<'a> Scanner<'a> {
     }
 
     /// Scan a directory for projects (git repos or folders with files)
-    fn scan_directory(&mut self, base_path: &PathBuf) -> Result<usize> {
+    fn scan_directory(&mut self, base_path: &Path) -> Result<usize> {
         if !base_path.exists() {
             return Ok(0);
         }
 
-        let exclude_patterns = &self.config.exclude_patterns;
-
         // Collect all project paths first
         let mut projects_to_add = Vec::new();
         let mut git_projects = std::collections::HashSet::new();
 
-        for entry in WalkDir::new(base_path)
-            .max_depth(self.config.max_depth)
-            .follow_links(false)
-            .into_iter()
-            .filter_entry(|e| {
-                let name = e.file_name().to_string_lossy();
-                // Skip hidden directories (except .git which we're looking for)
-                if name.starts_with('.') && name != ".git" {
-                    return false;
-                }
-                // Skip excluded patterns
-                !exclude_patterns.iter().any(|p| name.contains(p))
-            })
-        {
-            let entry = match entry {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
-
-            // Look for .git directories (high priority - always a project)
-            if entry.file_type().is_dir() && entry.file_name() == ".git" {
-                if let Some(parent) = entry.path().parent() {
+        // Look for .git directories (high priority - always a project)
+        for dir_path in self.directories(base_path, true) {
+            if dir_path.file_name().is_some_and(|n| n == ".git") {
+                if let Some(parent) = dir_path.parent() {
                     git_projects.insert(parent.to_path_buf());
                     projects_to_add.push(parent.to_path_buf());
                 }
@@ -96,26 +78,8 @@ impl<'a> Scanner<'a> {
         // and not inside other already-indexed non-git folders
         let mut non_git_projects = Vec::new();
 
-        for entry in WalkDir::new(base_path)
-            .max_depth(self.config.max_depth)
-            .follow_links(false)
-            .into_iter()
-            .filter_entry(|e| {
-                let name = e.file_name().to_string_lossy();
-                !name.starts_with('.')
-                    && !exclude_patterns.iter().any(|p| name.contains(p))
-            })
-        {
-            let entry = match entry {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
-
-            if !entry.file_type().is_dir() {
-                continue;
-            }
-
-            let dir_path = entry.path();
+        for dir_path in self.directories(base_path, false) {
+            let dir_path = dir_path.as_path();
 
             // Skip if already a git project or inside a git project
             if git_projects.contains(dir_path) {
@@ -127,15 +91,13 @@ impl<'a> Scanner<'a> {
 
             // Check if this directory contains files (not just subdirectories)
             if let Ok(contents) = std::fs::read_dir(dir_path) {
-                let has_files = contents
-                    .filter_map(|e| e.ok())
-                    .any(|e| {
-                        if let Ok(ft) = e.file_type() {
-                            ft.is_file() && !e.file_name().to_string_lossy().starts_with('.')
-                        } else {
-                            false
-                        }
-                    });
+                let has_files = contents.filter_map(|e| e.ok()).any(|e| {
+                    if let Ok(ft) = e.file_type() {
+                        ft.is_file() && !e.file_name().to_string_lossy().starts_with('.')
+                    } else {
+                        false
+                    }
+                });
 
                 if has_files {
                     non_git_projects.push(dir_path.to_path_buf());
@@ -149,9 +111,9 @@ impl<'a> Scanner<'a> {
             .iter()
             .filter(|path| {
                 // Keep this path only if no OTHER path is its child (descendant)
-                !non_git_projects.iter().any(|other| {
-                    other != *path && other.starts_with(*path)
-                })
+                !non_git_projects
+                    .iter()
+                    .any(|other| other != *path && other.starts_with(*path))
             })
             .cloned()
             .collect();
@@ -159,7 +121,76 @@ impl<'a> Scanner<'a> {
         projects_to_add.extend(filtered_non_git);
 
         // Batch insert for performance
-        self.db.upsert_projects_batch(&projects_to_add, ProjectSource::Scan)
+        self.upsert_with_members(&projects_to_add, ProjectSource::Scan)
+    }
+
+    /// Directories below base_path up to max_depth, without hidden ones (except
+    /// .git when include_git is set) and those matching an exclude pattern.
+    /// With scan_respect_ignore, directories ignored by .gitignore, .ignore or
+    /// .gotoignore files are skipped too.
+    fn directories(&self, base_path: &Path, include_git: bool) -> Vec<PathBuf> {
+        let exclude_patterns = self.config.exclude_patterns.clone();
+        let keep = move |name: &str| {
+            if name.starts_with('.') && !(include_git && name == ".git") {
+                return false;
+            }
+            !exclude_patterns.iter().any(|p| name.contains(p.as_str()))
+        };
+
+        if self.config.scan_respect_ignore {
+            walk::walker(base_path, self.config.max_depth)
+                .hidden(false)
+                .filter_entry(move |e| e.depth() == 0 || keep(&e.file_name().to_string_lossy()))
+                .build()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_some_and(|t| t.is_dir()))
+                .map(|e| e.into_path())
+                .collect()
+        } else {
+            WalkDir::new(base_path)
+                .max_depth(self.config.max_depth)
+                .follow_links(false)
+                .into_iter()
+                .filter_entry(|e| keep(&e.file_name().to_string_lossy()))
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_dir())
+                .map(|e| e.into_path())
+                .collect()
+        }
+    }
+
+    /// Insert projects, then the members of those that are workspaces
+    /// (Cargo, npm/yarn/pnpm, Go, Gradle) as child projects
+    fn upsert_with_members(
+        &mut self,
+        projects: &[PathBuf],
+        source: ProjectSource,
+    ) -> Result<usize> {
+        let mut count = self.db.upsert_projects_batch(projects, source.clone())?;
+
+        for root in projects {
+            let members: Vec<PathBuf> = workspace::members(root)
+                .into_iter()
+                .filter(|m| !self.is_excluded(root, m))
+                .collect();
+            if !members.is_empty() {
+                count += self.db.upsert_members(root, &members, source.clone())?;
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Check a workspace member against the exclude patterns (below its root)
+    fn is_excluded(&self, root: &Path, member: &Path) -> bool {
+        let relative = member.strip_prefix(root).unwrap_or(member);
+        relative.components().any(|c| {
+            let name = c.as_os_str().to_string_lossy();
+            self.config
+                .exclude_patterns
+                .iter()
+                .any(|p| name.contains(p.as_str()))
+        })
     }
 
     /// Scan for git repositories using macOS Spotlight (mdfind)
@@ -180,8 +211,8 @@ impl<'a> Scanner<'a> {
             "CMakeLists.txt",
             "Makefile",
             // Documentation projects
-            "docs.json",      // Mintlify
-            "mkdocs.yml",     // MkDocs
+            "docs.json",            // Mintlify
+            "mkdocs.yml",           // MkDocs
             "docusaurus.config.js", // Docusaurus
         ];
 
@@ -238,7 +269,12 @@ impl<'a> Scanner<'a> {
 
                     // Skip if path matches any exclude pattern
                     let path_str = project_dir.to_string_lossy();
-                    if self.config.exclude_patterns.iter().any(|p| path_str.contains(p)) {
+                    if self
+                        .config
+                        .exclude_patterns
+                        .iter()
+                        .any(|p| path_str.contains(p))
+                    {
                         continue;
                     }
 
@@ -249,7 +285,7 @@ impl<'a> Scanner<'a> {
         }
 
         // Batch insert for performance
-        self.db.upsert_projects_batch(&projects_to_add, ProjectSource::Spotlight)
+        self.upsert_with_members(&projects_to_add, ProjectSource::Spotlight)
     }
 }
 
@@ -1,73 +1,253 @@
 use anyhow::Result;
+use indicatif::{ProgressBar, ProgressStyle};
+use rayon::prelude::*;
+#[cfg(feature = "semantic")]
+use sha2::{Digest, Sha256};
+#[cfg(feature = "semantic")]
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
-
-use crate::db::Database;
-use crate::embedding::{embed_text, embed_texts};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::db::{Database, FingerprintRow, LexicalFields};
+#[cfg(feature = "semantic")]
+use crate::embedding::{embed_text, embed_texts, profile};
+use crate::git;
+use crate::manifest;
+use crate::symbols::{self, Symbol, SymbolKind, SOURCE_EXTENSIONS};
+use crate::walk;
+use crate::workspace;
 
 /// Maximum characters to read from README
 const README_MAX_CHARS: usize = 1500;
 
+/// Target size of a README chunk embedded as its own vector
+const README_CHUNK_CHARS: usize = 500;
+
+/// Maximum README chunks embedded per project
+const README_MAX_CHUNKS: usize = 6;
+
+/// Number of parent directory names added to the embedded text
+const PATH_HINT_DEPTH: usize = 3;
+
+/// Maximum number of embeddings kept in the cache (least recently used go first)
+#[cfg(feature = "semantic")]
+const EMBEDDING_CACHE_MAX_ENTRIES: usize = 50_000;
+
 /// Generic directory names to skip (not semantically meaningful)
 const GENERIC_DIRS: &[&str] = &[
     // Build/structure
-    "src", "lib", "bin", "cmd", "pkg", "app", "apps",
-    "main", "java", "kotlin", "scala", "resources",
-    "test", "tests", "spec", "specs", "integration",
+    "src",
+    "lib",
+    "bin",
+    "cmd",
+    "pkg",
+    "app",
+    "apps",
+    "main",
+    "java",
+    "kotlin",
+    "scala",
+    "resources",
+    "test",
+    "tests",
+    "spec",
+    "specs",
+    "integration",
     // Package prefixes (very common, not semantic)
-    "com", "org", "io", "net", "dev", "github",
+    "com",
+    "org",
+    "io",
+    "net",
+    "dev",
+    "github",
     // Generic code organization
-    "impl", "internal", "api", "core", "base",
-    "util", "utils", "helper", "helpers", "common", "shared",
-    "model", "models", "entity", "entities", "dto", "dtos",
-    "service", "services", "controller", "controllers",
-    "repository", "repositories", "dao", "daos",
+    "impl",
+    "internal",
+    "api",
+    "core",
+    "base",
+    "util",
+    "utils",
+    "helper",
+    "helpers",
+    "common",
+    "shared",
+    "model",
+    "models",
+    "entity",
+    "entities",
+    "dto",
+    "dtos",
+    "service",
+    "services",
+    "controller",
+    "controllers",
+    "repository",
+    "repositories",
+    "dao",
+    "daos",
     // Build output
-    "build", "dist", "target", "out", "output", "gen", "generated",
+    "build",
+    "dist",
+    "target",
+    "out",
+    "output",
+    "gen",
+    "generated",
     // Dependencies
-    "vendor", "node_modules", "deps", "dependencies", "third_party",
+    "vendor",
+    "node_modules",
+    "deps",
+    "dependencies",
+    "third_party",
     // Assets/config
-    "assets", "public", "static", "resources", "config", "configs",
-    "scripts", "tools", "templates", "fixtures",
+    "assets",
+    "public",
+    "static",
+    "resources",
+    "config",
+    "configs",
+    "scripts",
+    "tools",
+    "templates",
+    "fixtures",
     // Documentation
-    "docs", "doc", "documentation", "examples", "samples", "demo",
+    "docs",
+    "doc",
+    "documentation",
+    "examples",
+    "samples",
+    "demo",
     // Meta
-    "META-INF", "WEB-INF",
+    "META-INF",
+    "WEB-INF",
 ];
 
 /// Generic type names to skip
 const GENERIC_TYPES: &[&str] = &[
-    "App", "Main", "Application", "Program",
-    "Config", "Configuration", "Options", "Settings", "Properties",
-    "Utils", "Util", "Helper", "Helpers", "Common",
-    "Handler", "Manager", "Service", "Factory", "Builder", "Provider",
-    "Context", "State", "Store", "Cache",
-    "Error", "Exception", "Result",
-    "Test", "Tests", "Spec", "Mock",
-    "Base", "Abstract", "Default", "Simple", "Basic",
-    "Impl", "Implementation",
+    "App",
+    "Main",
+    "Application",
+    "Program",
+    "Config",
+    "Configuration",
+    "Options",
+    "Settings",
+    "Properties",
+    "Utils",
+    "Util",
+    "Helper",
+    "Helpers",
+    "Common",
+    "Handler",
+    "Manager",
+    "Service",
+    "Factory",
+    "Builder",
+    "Provider",
+    "Context",
+    "State",
+    "Store",
+    "Cache",
+    "Error",
+    "Exception",
+    "Result",
+    "Test",
+    "Tests",
+    "Spec",
+    "Mock",
+    "Base",
+    "Abstract",
+    "Default",
+    "Simple",
+    "Basic",
+    "Impl",
+    "Implementation",
 ];
 
-/// Source file extensions to scan for types
-const SOURCE_EXTENSIONS: &[&str] = &["rs", "java", "kt", "scala", "ts", "js", "go", "py", "cs"];
-
 /// Metadata extracted from a project
 #[derive(Debug, Default)]
 pub struct ProjectMetadata {
+    /// Name declared in the manifest (package, module path, artifact id)
+    pub package_name: Option<String>,
     pub description: Option<String>,
     pub readme_excerpt: Option<String>,
     pub tech_stack: Vec<String>,
     pub keywords: Vec<String>,
     pub structure_hints: Vec<String>,
     pub type_names: Vec<String>,
+    /// Types, traits, modules and public functions declared in source files
+    pub symbols: Vec<Symbol>,
+    /// README text split into paragraphs-sized pieces, each embedded separately
+    pub readme_chunks: Vec<String>,
+    /// Names of the parent directories (client, team, area), nearest first
+    pub path_hints: Vec<String>,
+}
+
+/// Which part of a project's metadata a vector was computed from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingField {
+    /// Everything together (the historical single vector)
+    Summary,
+    /// Name, description and keywords
+    Description,
+    /// One chunk of the README
+    Readme,
+    /// Technologies and derived hints (backend, frontend...)
+    Tech,
+    /// Semantic directory names
+    Structure,
+    /// Type/class names from source files
+    Types,
+}
+
+impl EmbeddingField {
+    #[cfg(feature = "semantic")]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmbeddingField::Summary => "summary",
+            EmbeddingField::Description => "description",
+            EmbeddingField::Readme => "readme",
+            EmbeddingField::Tech => "tech",
+            EmbeddingField::Structure => "structure",
+            EmbeddingField::Types => "types",
+        }
+    }
+
+    #[cfg(feature = "semantic")]
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "summary" => Some(EmbeddingField::Summary),
+            "description" => Some(EmbeddingField::Description),
+            "readme" => Some(EmbeddingField::Readme),
+            "tech" => Some(EmbeddingField::Tech),
+            "structure" => Some(EmbeddingField::Structure),
+            "types" => Some(EmbeddingField::Types),
+            _ => None,
+        }
+    }
+
+    /// Multiplier applied to the field's confidence score in max-sim.
+    /// Short fields match generic queries easily, so they count a bit less
+    /// than the description written by the author.
+    #[cfg(feature = "semantic")]
+    fn weight(&self) -> f32 {
+        match self {
+            EmbeddingField::Summary | EmbeddingField::Description => 1.0,
+            EmbeddingField::Readme => 0.95,
+            EmbeddingField::Types | EmbeddingField::Tech => 0.9,
+            EmbeddingField::Structure => 0.8,
+        }
+    }
 }
 
 impl ProjectMetadata {
     /// Build the text to be embedded
     pub fn to_embedding_text(&self, project_name: &str) -> String {
         let mut parts = vec![project_name.to_string()];
+        parts.extend(self.distinct_package_name(project_name));
 
         if let Some(desc) = &self.description {
             parts.push(desc.clone());
@@ -96,6 +276,11 @@ impl ProjectMetadata {
             parts.push(format!("Structure: {}", self.structure_hints.join(", ")));
         }
 
+        // Add parent directories, so `clientA api` can tell repos with the same name apart
+        if !self.path_hints.is_empty() {
+            parts.push(format!("Location: {}", self.path_hints.join(", ")));
+        }
+
         // Add type names from source files
         if !self.type_names.is_empty() {
             parts.push(format!("Types: {}", self.type_names.join(", ")));
@@ -103,6 +288,77 @@ impl ProjectMetadata {
 
         parts.join(" | ")
     }
+
+    /// Manifest name when it says more than the directory name
+    /// (`github.com/acme/billing` for `billing`, `acme/payments` for `api`)
+    fn distinct_package_name(&self, project_name: &str) -> Option<String> {
+        self.package_name
+            .as_ref()
+            .filter(|name| !name.eq_ignore_ascii_case(project_name))
+            .cloned()
+    }
+
+    /// Build the texts embedded as separate vectors, one per non-empty field
+    /// (the summary first, then each README chunk on its own)
+    pub fn to_field_texts(&self, project_name: &str) -> Vec<(EmbeddingField, String)> {
+        let mut fields = vec![(
+            EmbeddingField::Summary,
+            self.to_embedding_text(project_name),
+        )];
+
+        let mut description = vec![project_name.to_string()];
+        description.extend(self.distinct_package_name(project_name));
+        description.extend(self.description.iter().cloned());
+        if !self.keywords.is_empty() {
+            description.push(self.keywords.join(", "));
+        }
+        if !self.path_hints.is_empty() {
+            description.push(format!("Location: {}", self.path_hints.join(", ")));
+        }
+        if description.len() > 1 {
+            fields.push((EmbeddingField::Description, description.join(" | ")));
+        }
+
+        for chunk in &self.readme_chunks {
+            fields.push((
+                EmbeddingField::Readme,
+                format!("{} | {}", project_name, chunk),
+            ));
+        }
+
+        if !self.tech_stack.is_empty() {
+            let mut tech = format!(
+                "{} | Technologies: {}",
+                project_name,
+                self.tech_stack.join(", ")
+            );
+            let hints = derive_semantic_hints(&self.tech_stack);
+            if !hints.is_empty() {
+                tech.push_str(&format!(" | Type: {}", hints.join(", ")));
+            }
+            fields.push((EmbeddingField::Tech, tech));
+        }
+
+        if !self.structure_hints.is_empty() {
+            fields.push((
+                EmbeddingField::Structure,
+                format!(
+                    "{} | Structure: {}",
+                    project_name,
+                    self.structure_hints.join(", ")
+                ),
+            ));
+        }
+
+        if !self.type_names.is_empty() {
+            fields.push((
+                EmbeddingField::Types,
+                format!("{} | Types: {}", project_name, self.type_names.join(", ")),
+            ));
+        }
+
+        fields
+    }
 }
 
 /// Derive semantic hints from tech stack (backend, frontend, etc.)
@@ -110,15 +366,25 @@ fn derive_semantic_hints(tech_stack: &[String]) -> Vec<&'static str> {
     let mut hints = Vec::new();
 
     // Backend indicators
-    let backend_techs = ["Scala", "Java", "Kotlin", "Go", "Rust", "Python", "Ruby", "PHP", "Elixir", "C#", "F#"];
-    let has_backend = tech_stack.iter().any(|t| backend_techs.contains(&t.as_str()));
+    let backend_techs = [
+        "Scala", "Java", "Kotlin", "Go", "Rust", "Python", "Ruby", "PHP", "Elixir", "C#", "F#",
+    ];
+    let has_backend = tech_stack
+        .iter()
+        .any(|t| backend_techs.contains(&t.as_str()));
 
     // Frontend indicators
-    let frontend_techs = ["Next.js", "Nuxt", "Vite", "Astro", "Svelte", "Angular", "Vue", "Tailwind"];
-    let has_frontend = tech_stack.iter().any(|t| frontend_techs.contains(&t.as_str()));
+    let frontend_techs = [
+        "Next.js", "Nuxt", "Vite", "Astro", "Svelte", "Angular", "Vue", "Tailwind",
+    ];
+    let has_frontend = tech_stack
+        .iter()
+        .any(|t| frontend_techs.contains(&t.as_str()));
 
     // Check for web-specific patterns
-    let is_web_app = tech_stack.iter().any(|t| matches!(t.as_str(), "JavaScript" | "TypeScript"));
+    let is_web_app = tech_stack
+        .iter()
+        .any(|t| matches!(t.as_str(), "JavaScript" | "TypeScript"));
 
     // Determine type
     if has_frontend || (is_web_app && !has_backend) {
@@ -134,7 +400,10 @@ fn derive_semantic_hints(tech_stack: &[String]) -> Vec<&'static str> {
     }
 
     // Infrastructure
-    if tech_stack.iter().any(|t| matches!(t.as_str(), "Docker" | "Kubernetes" | "Terraform" | "Pulumi")) {
+    if tech_stack
+        .iter()
+        .any(|t| matches!(t.as_str(), "Docker" | "Kubernetes" | "Terraform" | "Pulumi"))
+    {
         hints.push("infrastructure");
         hints.push("devops");
     }
@@ -142,18 +411,32 @@ fn derive_semantic_hints(tech_stack: &[String]) -> Vec<&'static str> {
     hints
 }
 
+/// Names of the nearest parent directories, stopping at the home directory
+fn extract_path_hints(path: &Path) -> Vec<String> {
+    let home = dirs::home_dir().unwrap_or_default();
+
+    path.ancestors()
+        .skip(1)
+        .take_while(|dir| *dir != home && dir.parent().is_some())
+        .take(PATH_HINT_DEPTH)
+        .filter_map(|dir| dir.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .filter(|name| {
+            !name.starts_with('.') && !GENERIC_DIRS.contains(&name.to_lowercase().as_str())
+        })
+        .collect()
+}
+
 /// Extract semantic hints from directory structure
-fn extract_structure_hints(path: &Path) -> Vec<String> {
+fn extract_structure_hints(path: &Path, members: &[PathBuf]) -> Vec<String> {
     let mut names: HashSet<String> = HashSet::new();
 
-    // Walk directory tree up to depth 6
-    for entry in WalkDir::new(path)
-        .max_depth(6)
-        .follow_links(false)
-        .into_iter()
+    // Walk directory tree up to depth 6, skipping ignored and build directories
+    for entry in walk::extraction_walker(path, 6, &[], members)
+        .build()
         .filter_map(|e| e.ok())
     {
-        if !entry.file_type().is_dir() {
+        if !entry.file_type().is_some_and(|t| t.is_dir()) {
             continue;
         }
 
@@ -174,18 +457,6 @@ fn extract_structure_hints(path: &Path) -> Vec<String> {
             continue;
         }
 
-        // Skip paths containing build/vendor/node_modules
-        let path_str = entry.path().to_string_lossy().to_lowercase();
-        if path_str.contains("node_modules")
-            || path_str.contains("/target/")
-            || path_str.contains("/build/")
-            || path_str.contains("/dist/")
-            || path_str.contains("/vendor/")
-            || path_str.contains("/.git/")
-        {
-            continue;
-        }
-
         names.insert(name);
     }
 
@@ -196,18 +467,19 @@ fn extract_structure_hints(path: &Path) -> Vec<String> {
     result
 }
 
-/// Extract type names from largest source files
-fn extract_type_names(path: &Path) -> Vec<String> {
+/// Test code, left out of symbol extraction (gitignore syntax)
+const TEST_PATTERNS: &[&str] = &["test/", "tests/", "spec/", "*_test.*", "*.test.*"];
+
+/// The largest source files of a project (tests, vendored and generated code excluded)
+fn largest_source_files(path: &Path, members: &[PathBuf]) -> Vec<(PathBuf, u64)> {
     // Find source files with their sizes
-    let mut source_files: Vec<(std::path::PathBuf, u64)> = Vec::new();
+    let mut source_files: Vec<(PathBuf, u64)> = Vec::new();
 
-    for entry in WalkDir::new(path)
-        .max_depth(8)
-        .follow_links(false)
-        .into_iter()
+    for entry in walk::extraction_walker(path, 8, TEST_PATTERNS, members)
+        .build()
         .filter_map(|e| e.ok())
     {
-        if !entry.file_type().is_file() {
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
             continue;
         }
 
@@ -222,37 +494,26 @@ fn extract_type_names(path: &Path) -> Vec<String> {
             continue;
         }
 
-        // Skip test files and generated/vendor paths
-        let path_str = file_path.to_string_lossy().to_lowercase();
-        if path_str.contains("/test/")
-            || path_str.contains("/tests/")
-            || path_str.contains("/spec/")
-            || path_str.contains("_test.")
-            || path_str.contains(".test.")
-            || path_str.contains("node_modules")
-            || path_str.contains("/target/")
-            || path_str.contains("/build/")
-            || path_str.contains("/dist/")
-            || path_str.contains("/vendor/")
-            || path_str.contains("/generated/")
-            || path_str.contains("/.git/")
-        {
-            continue;
-        }
-
         if let Ok(metadata) = entry.metadata() {
             source_files.push((file_path.to_path_buf(), metadata.len()));
         }
     }
 
     // Sort by size descending, take top 10
-    source_files.sort_by(|a, b| b.1.cmp(&a.1));
+    source_files.sort_by_key(|f| std::cmp::Reverse(f.1));
     source_files.truncate(10);
+    source_files
+}
 
-    // Extract type names from these files
-    let mut type_names: HashSet<String> = HashSet::new();
+/// Most symbols kept per project
+const MAX_SYMBOLS: usize = 500;
+
+/// Extract declared symbols (types, traits, modules, public functions)
+/// from the largest source files
+fn extract_symbols(path: &Path, members: &[PathBuf]) -> Vec<Symbol> {
+    let mut result: Vec<Symbol> = Vec::new();
 
-    for (file_path, _) in source_files {
+    for (file_path, _) in largest_source_files(path, members) {
         if let Ok(content) = fs::read_to_string(&file_path) {
             // Limit content to first 50KB to avoid huge files (UTF-8 safe)
             let content = if content.len() > 50_000 {
@@ -267,8 +528,30 @@ fn extract_type_names(path: &Path) -> Vec<String> {
             };
 
             let ext = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            let extracted = extract_types_from_content(content, ext);
-            type_names.extend(extracted);
+            let file = file_path
+                .strip_prefix(path)
+                .unwrap_or(&file_path)
+                .to_path_buf();
+            for (name, kind) in symbols::extract(content, ext) {
+                result.push(Symbol {
+                    name,
+                    kind,
+                    file: file.clone(),
+                });
+            }
+        }
+    }
+
+    result.truncate(MAX_SYMBOLS);
+    result
+}
+
+/// Type and trait names for the "Types:" part of the embedded text
+fn type_names(symbols: &[Symbol]) -> Vec<String> {
+    let mut type_names: HashSet<String> = HashSet::new();
+    for symbol in symbols {
+        if matches!(symbol.kind, SymbolKind::Type | SymbolKind::Trait) {
+            type_names.insert(symbol.name.clone());
         }
     }
 
@@ -283,102 +566,124 @@ fn extract_type_names(path: &Path) -> Vec<String> {
     result
 }
 
-/// Extract type declarations from source code content
-fn extract_types_from_content(content: &str, ext: &str) -> Vec<String> {
-    let mut types = Vec::new();
-
-    // Patterns for different languages
-    let patterns: &[&str] = match ext {
-        // Java/Kotlin/Scala
-        "java" | "kt" | "scala" => &[
-            r"public\s+(?:class|interface|enum|record)\s+(\w+)",
-            r"class\s+(\w+)",
-        ],
-        // Rust
-        "rs" => &[
-            r"pub\s+struct\s+(\w+)",
-            r"pub\s+enum\s+(\w+)",
-            r"pub\s+trait\s+(\w+)",
-        ],
-        // TypeScript/JavaScript
-        "ts" | "js" => &[
-            r"export\s+(?:class|interface|type|enum)\s+(\w+)",
-            r"class\s+(\w+)",
-        ],
-        // Go
-        "go" => &[
-            r"type\s+([A-Z]\w+)\s+struct",
-            r"type\s+([A-Z]\w+)\s+interface",
-        ],
-        // Python
-        "py" => &[
-            r"class\s+(\w+)",
-        ],
-        // C#
-        "cs" => &[
-            r"public\s+(?:class|interface|enum|struct|record)\s+(\w+)",
-        ],
-        _ => return types,
-    };
-
-    for pattern in patterns {
-        if let Ok(re) = regex_lite::Regex::new(pattern) {
-            for cap in re.captures_iter(content) {
-                if let Some(name) = cap.get(1) {
-                    let type_name = name.as_str().to_string();
-                    // Only include if it starts with uppercase (convention for types)
-                    if type_name.chars().next().map(|c| c.is_uppercase()).unwrap_or(false) {
-                        types.push(type_name);
-                    }
-                }
-            }
-        }
+/// Extract metadata from a project directory
+///
+/// Workspace members are indexed as projects of their own, so the walks
+/// of a workspace root leave them out: the root would otherwise collect its
+/// members' types and outrank them.
+pub fn extract_metadata(path: &Path) -> ProjectMetadata {
+    let mut meta = ProjectMetadata::default();
+    let members = workspace::members(path);
+
+    // Name, description and keywords from build manifests
+    // (Cargo.toml, package.json, pyproject.toml, go.mod, pom.xml...)
+    let manifest = manifest::read(path);
+    meta.package_name = manifest.name;
+    meta.description = manifest.description;
+    meta.keywords = manifest.keywords;
+
+    // Read README excerpt and the chunks embedded separately
+    if let Some(content) = read_readme(path) {
+        meta.readme_excerpt = Some(extract_readme_text(&content, README_MAX_CHARS));
+        meta.readme_chunks = chunk_text(
+            &extract_readme_text(&content, README_CHUNK_CHARS * README_MAX_CHUNKS),
+            README_CHUNK_CHARS,
+        );
     }
 
-    types
+    // Detect tech stack
+    meta.tech_stack = detect_tech_stack(path);
+
+    // Extract structure hints from directory names
+    meta.structure_hints = extract_structure_hints(path, &members);
+
+    // Parent directory names (below the home directory)
+    meta.path_hints = extract_path_hints(path);
+
+    // Extract symbols from largest source files
+    meta.symbols = extract_symbols(path, &members);
+    meta.type_names = type_names(&meta.symbols);
+
+    meta
 }
 
-/// Extract metadata from a project directory
-pub fn extract_metadata(path: &Path) -> ProjectMetadata {
-    let mut meta = ProjectMetadata::default();
+/// Bumped when extraction changes, so every project gets extracted again
+const EXTRACTOR_VERSION: u32 = 5;
 
-    // Try to read description from package.json (Node.js)
-    if let Some(desc) = read_package_json_description(path) {
-        meta.description = Some(desc);
-    }
+/// Fingerprint of the files `extract_metadata` reads
+///
+/// Size and mtime of the top-level files (manifests, README, build markers,
+/// ignore files) and of the largest source files symbols are read from,
+/// top-level directory names, the checked-out commit and the git index.
+/// Files are stat'ed, never read. Workspace members are left out like in
+/// extraction, so they don't refresh their root.
+pub fn fingerprint(path: &Path) -> String {
+    let mut inputs = format!("v{}", EXTRACTOR_VERSION);
 
-    // Try to read description from Cargo.toml (Rust)
-    if meta.description.is_none() {
-        if let Some(desc) = read_cargo_toml_description(path) {
-            meta.description = Some(desc);
-        }
+    let mut entries: Vec<(String, String)> = fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') && !walk::IGNORE_FILES.contains(&name.as_str()) {
+                return None;
+            }
+            let metadata = e.metadata().ok()?;
+            // Directory mtimes change on every build (target/, node_modules/)
+            let stamp = if metadata.is_dir() {
+                "dir".to_string()
+            } else {
+                file_stamp(&metadata)
+            };
+            Some((name, stamp))
+        })
+        .collect();
+    entries.sort();
+    for (name, stamp) in entries {
+        inputs.push_str(&format!("|{}={}", name, stamp));
     }
 
-    // Try to read description from pyproject.toml (Python)
-    if meta.description.is_none() {
-        if let Some(desc) = read_pyproject_description(path) {
-            meta.description = Some(desc);
-        }
+    let mut sources: Vec<(PathBuf, String)> = largest_source_files(path, &workspace::members(path))
+        .into_iter()
+        .filter_map(|(file, _)| {
+            let stamp = file_stamp(&fs::metadata(&file).ok()?);
+            Some((file, stamp))
+        })
+        .collect();
+    sources.sort();
+    for (file, stamp) in sources {
+        let relative = file.strip_prefix(path).unwrap_or(&file);
+        inputs.push_str(&format!("|{}={}", relative.display(), stamp));
     }
 
-    // Read README excerpt
-    meta.readme_excerpt = read_readme_excerpt(path);
-
-    // Detect tech stack
-    meta.tech_stack = detect_tech_stack(path);
-
-    // Read keywords from Cargo.toml or package.json
-    meta.keywords = read_cargo_keywords(path)
-        .or_else(|| read_package_json_keywords(path))
-        .unwrap_or_default();
+    inputs.push_str(&format!(
+        "|HEAD={}",
+        git::head_commit(path).unwrap_or_default()
+    ));
+    if let Some(index) = git::index_file(path).and_then(|index| fs::metadata(index).ok()) {
+        inputs.push_str(&format!("|index={}", file_stamp(&index)));
+    }
 
-    // Extract structure hints from directory names
-    meta.structure_hints = extract_structure_hints(path);
+    format!("{:016x}", fnv1a(inputs.as_bytes()))
+}
 
-    // Extract type names from largest source files
-    meta.type_names = extract_type_names(path);
+/// Size and modification time of a file
+fn file_stamp(metadata: &fs::Metadata) -> String {
+    let mtime = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    format!("{}:{}", metadata.len(), mtime)
+}
 
-    meta
+/// 64-bit FNV-1a: stable across Rust versions, unlike DefaultHasher
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
 }
 
 /// Detect technologies used in the project
@@ -425,6 +730,7 @@ fn detect_tech_stack(path: &Path) -> Vec<String> {
         ("Package.swift", ".swift", "Swift"),
         ("Podfile", ".swift", "iOS"),
         ("build.gradle", ".kt", "Android"),
+        ("pubspec.yaml", ".dart", "Dart"),
         // .NET (checked via extension scan below)
         // Infra
         ("Dockerfile", "", "Docker"),
@@ -448,6 +754,7 @@ fn detect_tech_stack(path: &Path) -> Vec<String> {
         // Data
         ("dbt_project.yml", ".sql", "dbt"),
         // Other
+        ("flake.nix", "", "Nix"),
         ("Makefile", "", "Make"),
         ("justfile", "", "Just"),
         ("Taskfile.yml", "", "Task"),
@@ -484,6 +791,7 @@ fn detect_tech_stack(path: &Path) -> Vec<String> {
         (".cpp", "C++"),
         (".cc", "C++"),
         (".zig", "Zig"),
+        (".dart", "Dart"),
         (".lua", "Lua"),
         (".clj", "Clojure"),
         (".erl", "Erlang"),
@@ -493,7 +801,12 @@ fn detect_tech_stack(path: &Path) -> Vec<String> {
     ];
 
     // Check src directory and root for extensions
-    for dir in [path, &path.join("src"), &path.join("lib"), &path.join("app")] {
+    for dir in [
+        path,
+        &path.join("src"),
+        &path.join("lib"),
+        &path.join("app"),
+    ] {
         if !dir.is_dir() {
             continue;
         }
@@ -513,93 +826,39 @@ fn detect_tech_stack(path: &Path) -> Vec<String> {
     stack
 }
 
-/// Read description from package.json
-fn read_package_json_description(path: &Path) -> Option<String> {
-    let pkg_path = path.join("package.json");
-    let content = fs::read_to_string(pkg_path).ok()?;
-    extract_json_string(&content, "description")
-}
-
-/// Read description from Cargo.toml
-fn read_cargo_toml_description(path: &Path) -> Option<String> {
-    let cargo_path = path.join("Cargo.toml");
-    let content = fs::read_to_string(cargo_path).ok()?;
-    let value: toml::Value = content.parse().ok()?;
-    // Try [package] first, then [workspace.package]
-    value.get("package")
-        .or_else(|| value.get("workspace")?.get("package"))?
-        .get("description")?
-        .as_str()
-        .map(String::from)
-}
-
-/// Read keywords from Cargo.toml
-fn read_cargo_keywords(path: &Path) -> Option<Vec<String>> {
-    let cargo_path = path.join("Cargo.toml");
-    let content = fs::read_to_string(cargo_path).ok()?;
-    let value: toml::Value = content.parse().ok()?;
-    // Try [package] first, then [workspace.package]
-    let pkg = value.get("package")
-        .or_else(|| value.get("workspace")?.get("package"))?;
-    let keywords = pkg.get("keywords")?.as_array()?;
-    let result: Vec<String> = keywords
-        .iter()
-        .filter_map(|v| v.as_str().map(String::from))
-        .collect();
-    if result.is_empty() { None } else { Some(result) }
-}
-
-/// Read keywords from package.json
-fn read_package_json_keywords(path: &Path) -> Option<Vec<String>> {
-    let pkg_path = path.join("package.json");
-    let content = fs::read_to_string(pkg_path).ok()?;
-    // Simple extraction - look for "keywords": [...]
-    let start = content.find("\"keywords\"")?;
-    let after = &content[start..];
-    let arr_start = after.find('[')?;
-    let arr_end = after.find(']')?;
-    let arr_content = &after[arr_start + 1..arr_end];
-    let keywords: Vec<String> = arr_content
-        .split(',')
-        .filter_map(|s| {
-            let trimmed = s.trim().trim_matches('"');
-            if trimmed.is_empty() { None } else { Some(trimmed.to_string()) }
-        })
-        .collect();
-    if keywords.is_empty() { None } else { Some(keywords) }
-}
-
-/// Read description from pyproject.toml
-fn read_pyproject_description(path: &Path) -> Option<String> {
-    let py_path = path.join("pyproject.toml");
-    let content = fs::read_to_string(py_path).ok()?;
-    let value: toml::Value = content.parse().ok()?;
-
-    // Try [project] section first (PEP 621), then poetry
-    let project = value.get("project")
-        .or_else(|| value.get("tool")?.get("poetry"))?;
+/// Read the README file
+fn read_readme(path: &Path) -> Option<String> {
+    let readme_names = ["README.md", "README", "readme.md", "Readme.md"];
 
-    project.get("description")?
-        .as_str()
-        .map(String::from)
+    readme_names
+        .iter()
+        .find_map(|name| fs::read_to_string(path.join(name)).ok())
 }
 
-/// Read first paragraph from README
-fn read_readme_excerpt(path: &Path) -> Option<String> {
-    let readme_names = ["README.md", "README", "readme.md", "Readme.md"];
+/// Split text into pieces of about `max_chars`, on word boundaries
+fn chunk_text(text: &str, max_chars: usize) -> Vec<String> {
+    let text = text.strip_suffix("...").unwrap_or(text);
+    let mut chunks = Vec::new();
+    let mut current = String::new();
 
-    for name in readme_names {
-        let readme_path = path.join(name);
-        if let Ok(content) = fs::read_to_string(&readme_path) {
-            return Some(extract_first_paragraph(&content));
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.len() + word.len() + 1 > max_chars {
+            chunks.push(std::mem::take(&mut current));
         }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
     }
 
-    None
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
 }
 
-/// Extract meaningful content from markdown README
-fn extract_first_paragraph(content: &str) -> String {
+/// Extract meaningful content from markdown README (up to `max_chars`)
+fn extract_readme_text(content: &str, max_chars: usize) -> String {
     // First, strip all HTML tags
     let stripped = strip_html_tags(content);
 
@@ -621,7 +880,8 @@ fn extract_first_paragraph(content: &str) -> String {
             || trimmed.starts_with("<!--")    // comments
             || trimmed.starts_with("* ")      // bullet points (with space)
             || trimmed.starts_with("- ")      // bullet points (with space)
-            || trimmed.contains("shields.io") // badges
+            || trimmed.contains("shields.io")
+        // badges
         {
             continue;
         }
@@ -632,15 +892,15 @@ fn extract_first_paragraph(content: &str) -> String {
         }
         result.push_str(trimmed);
 
-        if result.len() >= README_MAX_CHARS {
+        if result.len() >= max_chars {
             break;
         }
     }
 
     // Truncate to max chars (UTF-8 safe)
-    if result.len() > README_MAX_CHARS {
+    if result.len() > max_chars {
         // Find a safe truncation point (char boundary)
-        let mut end = README_MAX_CHARS;
+        let mut end = max_chars;
         while !result.is_char_boundary(end) && end > 0 {
             end -= 1;
         }
@@ -672,81 +932,465 @@ fn strip_html_tags(content: &str) -> String {
     result
 }
 
-// Simple JSON extraction without serde_json
-fn extract_json_string(json: &str, key: &str) -> Option<String> {
-    let pattern = format!("\"{}\"", key);
-    let start = json.find(&pattern)?;
-    let after_key = &json[start + pattern.len()..];
+/// index_info key of the profile the stored vectors were computed with
+#[cfg(feature = "semantic")]
+pub const PROFILE_INFO_KEY: &str = "embedding_profile";
+
+/// Make sure stored vectors match the current embedding profile
+///
+/// When the model or its text formatting changed, vectors computed the old
+/// way can't be compared with new queries: they are dropped so every project
+/// gets re-embedded. Returns true when that happened.
+#[cfg(feature = "semantic")]
+pub fn sync_profile(db: &Database) -> Result<bool> {
+    let current = profile().key();
+    let stored = db.get_info(PROFILE_INFO_KEY)?;
+    if stored.as_deref() == Some(current.as_str()) {
+        return Ok(false);
+    }
+
+    // A new vector dimension already emptied the index when the database opened
+    let (indexed, total) = db.embedding_stats()?;
+    if indexed > 0 {
+        db.invalidate_embeddings()?;
+    }
+    db.set_info(PROFILE_INFO_KEY, &current)?;
+    Ok(indexed > 0 || (stored.is_some() && total > 0))
+}
+
+/// What the index is built with: the embedding profile, or full-text only
+#[cfg(feature = "semantic")]
+pub fn profile_key() -> String {
+    profile().key()
+}
+
+#[cfg(not(feature = "semantic"))]
+pub fn profile_key() -> String {
+    "full-text".to_string()
+}
+
+/// What `index_projects` did
+#[derive(Debug, Default)]
+pub struct IndexReport {
+    /// Projects indexed for the first time
+    pub added: usize,
+    /// Indexed projects whose files changed, extracted and embedded again
+    pub refreshed: usize,
+    /// Why embedding stopped partway: the remaining projects were indexed
+    /// for full-text search only and get embedded by the next update
+    pub embedding_error: Option<String>,
+}
+
+/// Projects extracted, embedded and committed together. Bounds memory use,
+/// and an interrupted run resumes after the last committed batch.
+const INDEX_BATCH_SIZE: usize = 64;
 
-    // Skip whitespace and colon
-    let value_start = after_key.find('"')? + 1;
-    let rest = &after_key[value_start..];
-    let value_end = rest.find('"')?;
+/// A project waiting to be indexed: (id, path, name, fingerprint of its files)
+type PendingProject = (i64, PathBuf, String, String);
 
-    Some(rest[..value_end].to_string())
+/// Time spent in each indexing phase
+#[derive(Debug, Default)]
+struct PhaseTimings {
+    fingerprint: Duration,
+    extract: Duration,
+    embed: Duration,
+    store: Duration,
 }
 
+/// Index new projects and refresh those whose fingerprint changed
+///
+/// Metadata is extracted in parallel, then projects are embedded and stored
+/// in batches of INDEX_BATCH_SIZE, each in its own transaction.
+pub fn index_projects(db: &Database) -> Result<IndexReport> {
+    let mut timings = PhaseTimings::default();
 
-/// Index all unindexed projects
-pub fn index_projects(db: &Database) -> Result<usize> {
+    // Fingerprint new and indexed projects under one progress bar
+    let started = Instant::now();
     let unindexed = db.get_unindexed_projects()?;
+    let indexed = db.get_fingerprints()?;
+    let progress = progress_bar((unindexed.len() + indexed.len()) as u64, "Checking");
+    progress.set_message("fingerprinting");
+    let fingerprint_all = |paths: Vec<&Path>| -> Vec<String> {
+        paths
+            .into_par_iter()
+            .map(|path| {
+                let fingerprint = fingerprint(path);
+                progress.inc(1);
+                fingerprint
+            })
+            .collect()
+    };
+    let new_fingerprints = fingerprint_all(
+        unindexed
+            .iter()
+            .map(|(_, path, _)| path.as_path())
+            .collect(),
+    );
+    let current = fingerprint_all(
+        indexed
+            .iter()
+            .map(|(_, path, _, _)| path.as_path())
+            .collect(),
+    );
+    progress.finish_and_clear();
+
+    let stale = stale_projects(db, indexed, current)?;
+    let mut report = IndexReport {
+        added: unindexed.len(),
+        refreshed: stale.len(),
+        embedding_error: None,
+    };
 
-    if unindexed.is_empty() {
-        return Ok(0);
+    let mut pending: Vec<PendingProject> = unindexed
+        .into_iter()
+        .zip(new_fingerprints)
+        .map(|((id, path, name), fingerprint)| (id, path, name, fingerprint))
+        .collect();
+    pending.extend(stale);
+    timings.fingerprint = started.elapsed();
+
+    if pending.is_empty() {
+        return Ok(report);
     }
 
-    eprintln!(
-        "\x1b[36m⏳\x1b[0m Indexing {} projects semantically...",
-        unindexed.len()
-    );
+    let progress = progress_bar(pending.len() as u64, "Indexing");
+    for batch in pending.chunks(INDEX_BATCH_SIZE) {
+        index_batch(
+            db,
+            batch,
+            &progress,
+            &mut timings,
+            &mut report.embedding_error,
+        )?;
+    }
+    progress.finish_and_clear();
 
-    // Extract metadata and build texts for embedding
-    let mut texts: Vec<String> = Vec::with_capacity(unindexed.len());
-    let mut project_data: Vec<(i64, ProjectMetadata)> = Vec::with_capacity(unindexed.len());
+    if let Some(error) = &report.embedding_error {
+        eprintln!(
+            "\x1b[33m⚠\x1b[0m Embedding failed, indexed for full-text search only: {}",
+            error
+        );
+    }
 
-    for (id, path, name) in &unindexed {
-        let meta = extract_metadata(path);
-        let text = meta.to_embedding_text(name);
-        texts.push(text);
-        project_data.push((*id, meta));
+    let mut phases = vec![
+        ("fingerprint", timings.fingerprint),
+        ("extract", timings.extract),
+    ];
+    if cfg!(feature = "semantic") {
+        phases.push(("embed", timings.embed));
     }
+    phases.push(("store", timings.store));
+    let phases: Vec<String> = phases
+        .iter()
+        .map(|(phase, duration)| format!("{} {:.1}s", phase, duration.as_secs_f32()))
+        .collect();
+    eprintln!("\x1b[90m  {}\x1b[0m", phases.join(" · "));
 
-    // Generate embeddings in batch
-    let embeddings = embed_texts(&texts)?;
+    Ok(report)
+}
 
-    // Store in database
-    for ((id, meta), (embedding, text)) in project_data.iter().zip(embeddings.iter().zip(texts.iter())) {
+/// Extract, embed and store one batch of projects, committed as a whole
+///
+/// Once embedding fails (e.g. the embedding server is down), this and later
+/// batches are stored without vectors, so they stay searchable by full text
+/// and count as unindexed until an update embeds them.
+fn index_batch(
+    db: &Database,
+    batch: &[PendingProject],
+    progress: &ProgressBar,
+    timings: &mut PhaseTimings,
+    embedding_error: &mut Option<String>,
+) -> Result<()> {
+    // Extract metadata in parallel and build one text per field for embedding
+    progress.set_message("extracting");
+    let started = Instant::now();
+    let extracted: Vec<(ProjectMetadata, Vec<EmbeddingField>, Vec<String>)> = batch
+        .par_iter()
+        .map(|(_, path, name, _)| {
+            let meta = extract_metadata(path);
+            let (fields, texts): (Vec<_>, Vec<_>) = meta.to_field_texts(name).into_iter().unzip();
+            (meta, fields, texts)
+        })
+        .collect();
+    timings.extract += started.elapsed();
+
+    // Generate embeddings for the batch (unchanged texts come from the cache)
+    #[cfg(feature = "semantic")]
+    let embeddings = if embedding_error.is_some() {
+        None
+    } else {
+        progress.set_message("embedding");
+        let started = Instant::now();
+        let texts: Vec<String> = extracted
+            .iter()
+            .flat_map(|(_, _, texts)| texts.iter().cloned())
+            .collect();
+        let embeddings = embed_documents(db, &texts);
+        timings.embed += started.elapsed();
+        embeddings
+            .map_err(|e| *embedding_error = Some(format!("{:#}", e)))
+            .ok()
+    };
+    #[cfg(not(feature = "semantic"))]
+    let _ = embedding_error;
+
+    // Store in database (each project owns the next fields.len() embeddings;
+    // the first one is the summary)
+    progress.set_message("storing");
+    let started = Instant::now();
+    let tx = db.begin()?;
+    #[cfg(feature = "semantic")]
+    let mut offset = 0;
+    for ((id, _, name, fingerprint), (meta, fields, texts)) in batch.iter().zip(&extracted) {
         db.upsert_metadata(
             *id,
             meta.description.as_deref(),
             meta.readme_excerpt.as_deref(),
-            text,
+            &meta.tech_stack,
+            &texts[0],
+            fingerprint,
+        )?;
+        db.replace_symbols(*id, &meta.symbols)?;
+
+        // Every symbol name is searchable, not just the types that get embedded
+        let mut types: Vec<String> = Vec::new();
+        for symbol in &meta.symbols {
+            if !types.contains(&symbol.name) {
+                types.push(symbol.name.clone());
+            }
+        }
+        types.extend(meta.structure_hints.iter().cloned());
+        let mut keywords = meta.keywords.clone();
+        keywords.extend(meta.package_name.iter().cloned());
+        db.upsert_lexical(
+            *id,
+            &LexicalFields {
+                name,
+                description: meta.description.as_deref(),
+                keywords: &keywords,
+                readme: meta.readme_excerpt.as_deref(),
+                types: &types,
+                tech: &meta.tech_stack,
+            },
         )?;
 
-        db.upsert_embedding(*id, embedding)?;
+        #[cfg(feature = "semantic")]
+        match &embeddings {
+            Some(embeddings) => {
+                let vectors: Vec<(&str, &[f32])> = fields
+                    .iter()
+                    .zip(&embeddings[offset..offset + fields.len()])
+                    .map(|(field, embedding)| (field.as_str(), embedding.as_slice()))
+                    .collect();
+                db.upsert_embeddings(*id, &vectors)?;
+                offset += fields.len();
+            }
+            // Vectors of the old files would be kept under the new fingerprint
+            None => db.delete_embeddings(*id)?,
+        }
+        #[cfg(not(feature = "semantic"))]
+        let _ = fields;
     }
+    tx.commit()?;
+    timings.store += started.elapsed();
 
-    Ok(unindexed.len())
+    progress.inc(batch.len() as u64);
+    Ok(())
 }
 
+/// Progress bar on stderr with ETA (hidden when stderr isn't a terminal)
+fn progress_bar(len: u64, prefix: &'static str) -> ProgressBar {
+    let progress = ProgressBar::new(len);
+    progress.set_style(
+        ProgressStyle::with_template(
+            "\x1b[36m⏳\x1b[0m {prefix} [{bar:30}] {pos}/{len} \x1b[90m{msg} · ETA {eta}\x1b[0m",
+        )
+        .expect("valid progress template")
+        .progress_chars("=> "),
+    );
+    progress.set_prefix(prefix);
+    progress
+}
+
+/// Indexed projects whose files changed since, with their new fingerprint.
+/// Projects indexed before fingerprints existed just get theirs recorded.
+fn stale_projects(
+    db: &Database,
+    indexed: Vec<FingerprintRow>,
+    current: Vec<String>,
+) -> Result<Vec<PendingProject>> {
+    let mut stale = Vec::new();
+    for ((id, path, name, stored), current) in indexed.into_iter().zip(current) {
+        match stored {
+            Some(stored) if stored == current => {}
+            Some(_) => stale.push((id, path, name, current)),
+            None => db.set_fingerprint(id, &current)?,
+        }
+    }
+    Ok(stale)
+}
+
+/// Cache key of a text: SHA-256 of its exact bytes
+#[cfg(feature = "semantic")]
+fn text_hash(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+/// Embed a query, reusing the vector computed the last time it was typed
+#[cfg(feature = "semantic")]
+fn embed_query(db: &Database, query: &str) -> Result<Vec<f32>> {
+    let query = profile().format_query(query);
+    let hash = text_hash(&query);
+    if let Some(cached) = db
+        .get_cached_embeddings(&profile().model_id, std::slice::from_ref(&hash))?
+        .remove(&hash)
+    {
+        return Ok(cached);
+    }
+
+    let embedding = embed_text(&query)?;
+    db.put_cached_embeddings(
+        &profile().model_id,
+        &[(hash.as_str(), embedding.as_slice())],
+    )?;
+    Ok(embedding)
+}
+
+/// Embed documents, running the model only on texts not embedded before
+#[cfg(feature = "semantic")]
+fn embed_documents(db: &Database, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+    let texts: Vec<String> = texts.iter().map(|t| profile().format_passage(t)).collect();
+    let hashes: Vec<String> = texts.iter().map(|t| text_hash(t)).collect();
+    let mut cached = db.get_cached_embeddings(&profile().model_id, &hashes)?;
+
+    // Embed each distinct missing text once
+    let mut seen = HashSet::new();
+    let missing: Vec<(String, String)> = hashes
+        .iter()
+        .zip(&texts)
+        .filter(|(hash, _)| !cached.contains_key(*hash) && seen.insert(*hash))
+        .map(|(hash, text)| (hash.clone(), text.clone()))
+        .collect();
+
+    if !missing.is_empty() {
+        let missing_texts: Vec<String> = missing.iter().map(|(_, t)| t.clone()).collect();
+        let embeddings = embed_texts(&missing_texts)?;
+
+        let entries: Vec<(&str, &[f32])> = missing
+            .iter()
+            .zip(&embeddings)
+            .map(|((hash, _), embedding)| (hash.as_str(), embedding.as_slice()))
+            .collect();
+        db.put_cached_embeddings(&profile().model_id, &entries)?;
+        db.prune_embedding_cache(EMBEDDING_CACHE_MAX_ENTRIES)?;
+
+        for ((hash, _), embedding) in missing.into_iter().zip(embeddings) {
+            cached.insert(hash, embedding);
+        }
+    }
+
+    Ok(hashes.iter().map(|h| cached[h].clone()).collect())
+}
+
+/// Nearest vectors fetched per requested project (projects own several vectors)
+#[cfg(feature = "semantic")]
+const VECTORS_PER_RESULT: usize = 8;
+
+/// sqlite-vec upper bound for k in a KNN query
+#[cfg(feature = "semantic")]
+const MAX_KNN: usize = 4096;
+
 /// Perform semantic search
-pub fn semantic_search(db: &Database, query: &str, limit: usize) -> Result<Vec<(crate::db::Project, f32)>> {
+///
+/// A project scores the best weighted confidence among its vectors
+/// (max-sim), so a query matching only its type names or a single README
+/// paragraph still finds it.
+#[cfg(feature = "semantic")]
+pub fn semantic_search(
+    db: &Database,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<(crate::db::Project, f32)>> {
     // Embed the query
-    let query_embedding = embed_text(query)?;
+    let query_embedding = embed_query(db, query)?;
+
+    // Find similar vectors
+    let k = (limit * VECTORS_PER_RESULT).min(MAX_KNN);
+    let similar = db.find_similar(&query_embedding, k)?;
+
+    // Keep the best field score per project
+    let mut best: HashMap<i64, f32> = HashMap::new();
+    for (project_id, field, distance) in similar {
+        let weight = EmbeddingField::from_str(&field)
+            .map(|f| f.weight())
+            .unwrap_or(1.0);
+        let score = profile().calibrated_score(distance) * weight;
+        let entry = best.entry(project_id).or_insert(0.0);
+        *entry = entry.max(score);
+    }
 
-    // Find similar projects
-    let similar = db.find_similar(&query_embedding, limit)?;
+    let mut ranked: Vec<(i64, f32)> = best.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    ranked.truncate(limit);
 
     // Convert to projects with scores
-    let mut results = Vec::with_capacity(similar.len());
-    for (project_id, distance) in similar {
+    let mut results = Vec::with_capacity(ranked.len());
+    for (project_id, score) in ranked {
         if let Some(project) = db.get_project_by_id(project_id)? {
-            // Convert distance to similarity score (0-100)
-            // sqlite-vec uses L2 distance, so we need to convert
-            let similarity = (1.0 / (1.0 + distance)) * 100.0;
-            results.push((project, similarity));
+            results.push((project, score));
         }
     }
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    /// Move a file's mtime, since tests run faster than the 1s resolution
+    fn touch(path: &Path, seconds_ago: u64) {
+        let file = fs::File::options().append(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(seconds_ago))
+            .unwrap();
+    }
+
+    #[test]
+    fn fingerprint_follows_top_level_files_sources_and_git_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join(".git/HEAD"), "0123abcd\n").unwrap();
+        fs::write(root.join(".git/index"), "").unwrap();
+        fs::write(root.join("README.md"), "# demo\n").unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), "pub struct Demo;\n").unwrap();
+        for file in [".git/index", "README.md", "src/lib.rs"] {
+            touch(&root.join(file), 100);
+        }
+
+        let before = fingerprint(root);
+        assert_eq!(fingerprint(root), before);
+
+        // A source edit git hasn't seen yet: new size, then only a new mtime
+        fs::write(
+            root.join("src/lib.rs"),
+            "pub struct Demo;\npub struct Other;\n",
+        )
+        .unwrap();
+        touch(&root.join("src/lib.rs"), 100);
+        let after_edit = fingerprint(root);
+        assert_ne!(after_edit, before);
+        touch(&root.join("src/lib.rs"), 10);
+        let after_touch = fingerprint(root);
+        assert_ne!(after_touch, after_edit);
+
+        touch(&root.join(".git/index"), 10);
+        let after_index = fingerprint(root);
+        assert_ne!(after_index, after_touch);
+
+        touch(&root.join("README.md"), 10);
+        assert_ne!(fingerprint(root), after_index);
+    }
+}
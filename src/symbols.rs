@@ -0,0 +1,402 @@
+use regex_lite::Regex;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::lexical;
+
+/// What a declared symbol is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    /// Struct, class, enum, record, object, typedef
+    Type,
+    /// Trait, interface, protocol, type class
+    Trait,
+    /// Module, namespace, package
+    Module,
+    /// Top-level public function
+    Function,
+}
+
+impl SymbolKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SymbolKind::Type => "type",
+            SymbolKind::Trait => "trait",
+            SymbolKind::Module => "module",
+            SymbolKind::Function => "function",
+        }
+    }
+
+    /// Kind stored in the database by `as_str`
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "type" => Some(SymbolKind::Type),
+            "trait" => Some(SymbolKind::Trait),
+            "module" => Some(SymbolKind::Module),
+            "function" => Some(SymbolKind::Function),
+            _ => None,
+        }
+    }
+}
+
+/// A symbol declared in one of a project's source files
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// File declaring it, relative to the project root
+    pub file: PathBuf,
+}
+
+/// Source file extensions symbols are extracted from
+pub const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "java", "kt", "scala", "ts", "js", "go", "py", "cs", "rb", "php", "swift", "c", "h",
+    "cc", "cpp", "hpp", "ex", "exs", "hs", "zig", "lua",
+];
+
+/// Words that look like a C function name to the regex but aren't
+const C_KEYWORDS: &[&str] = &["if", "for", "while", "switch", "return", "sizeof", "else"];
+
+use SymbolKind::{Function, Module, Trait, Type};
+
+/// Declaration patterns per language; group 1 is the symbol name.
+/// Functions and modules only count at the top level (no indentation).
+fn patterns(ext: &str) -> &'static [(SymbolKind, &'static str)] {
+    match ext {
+        "rs" => &[
+            (Type, r"(?m)^\s*pub\s+(?:struct|enum|union|type)\s+(\w+)"),
+            (Trait, r"(?m)^\s*pub\s+(?:unsafe\s+)?trait\s+(\w+)"),
+            (Module, r"(?m)^pub\s+mod\s+(\w+)"),
+            (
+                Function,
+                r"(?m)^pub\s+(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?fn\s+(\w+)",
+            ),
+        ],
+        "java" => &[
+            (Type, r"\b(?:class|enum|record)\s+(\w+)"),
+            (Trait, r"\binterface\s+(\w+)"),
+        ],
+        // `data class`, `sealed class` and `enum class` all end in `class X`
+        "kt" => &[
+            (Type, r"\b(?:class|object)\s+(\w+)"),
+            (Trait, r"\binterface\s+(\w+)"),
+            (
+                Function,
+                r"(?m)^(?:public\s+)?(?:inline\s+)?(?:suspend\s+)?fun\s+(?:<[^>]*>\s*)?(?:\w+\.)?(\w+)\s*\(",
+            ),
+        ],
+        "scala" => &[
+            (Type, r"\b(?:class|object|enum)\s+(\w+)"),
+            (Trait, r"\btrait\s+(\w+)"),
+            (Module, r"(?m)^package\s+object\s+(\w+)"),
+        ],
+        "ts" | "js" => &[
+            (Type, r"\b(?:class|enum)\s+(\w+)"),
+            (Type, r"(?m)^export\s+type\s+(\w+)"),
+            (Trait, r"(?m)^export\s+(?:default\s+)?interface\s+(\w+)"),
+            (
+                Function,
+                r"(?m)^export\s+(?:default\s+)?(?:async\s+)?function\s*\*?\s*(\w+)",
+            ),
+        ],
+        "go" => &[
+            (Type, r"(?m)^type\s+([A-Z]\w*)\s+struct"),
+            (Trait, r"(?m)^type\s+([A-Z]\w*)\s+interface"),
+            (Function, r"(?m)^func\s+([A-Z]\w*)\s*[\[(]"),
+        ],
+        "py" => &[
+            (Type, r"(?m)^class\s+(\w+)"),
+            (Function, r"(?m)^(?:async\s+)?def\s+([a-zA-Z]\w*)"),
+        ],
+        "cs" => &[
+            (
+                Type,
+                r"\bpublic\s+(?:(?:static|sealed|abstract|partial|readonly)\s+)*(?:class|struct|enum|record)\s+(\w+)",
+            ),
+            (Trait, r"\bpublic\s+(?:partial\s+)?interface\s+(\w+)"),
+            (Module, r"(?m)^namespace\s+([\w.]+)"),
+        ],
+        // Methods are indented inside classes and modules
+        "rb" => &[
+            (Type, r"(?m)^\s*class\s+([A-Z][\w:]*)"),
+            (Module, r"(?m)^\s*module\s+([A-Z][\w:]*)"),
+            (Function, r"(?m)^\s*def\s+(?:self\.)?([a-z_]\w*[?!]?)"),
+        ],
+        "php" => &[
+            (
+                Type,
+                r"(?m)^\s*(?:(?:abstract|final|readonly)\s+)*(?:class|enum)\s+(\w+)",
+            ),
+            (Trait, r"(?m)^\s*(?:interface|trait)\s+(\w+)"),
+            (Module, r"(?m)^namespace\s+([\w\\]+)"),
+            (Function, r"(?m)^function\s+(\w+)"),
+        ],
+        "swift" => &[
+            (
+                Type,
+                r"(?m)^\s*(?:(?:public|open|internal|final)\s+)*(?:class|struct|enum|actor)\s+(\w+)",
+            ),
+            (
+                Trait,
+                r"(?m)^\s*(?:(?:public|open|internal)\s+)?protocol\s+(\w+)",
+            ),
+            (Function, r"(?m)^(?:public\s+)?func\s+(\w+)"),
+        ],
+        "c" | "h" => &[
+            (
+                Type,
+                r"(?m)^(?:typedef\s+)?(?:struct|union|enum)\s+(\w+)\s*\{",
+            ),
+            (Type, r"(?m)^\}\s*(\w+)\s*;"),
+            (
+                Function,
+                r"(?m)^(?:extern\s+)?(?:inline\s+)?(?:const\s+)?(?:unsigned\s+|signed\s+|struct\s+)?\w+[\s*]+(\w+)\s*\([^;{}]*\)\s*\{",
+            ),
+        ],
+        "cc" | "cpp" | "hpp" => &[
+            (
+                Type,
+                r"(?m)^\s*(?:template\s*<[^>]*>\s*)?(?:class|struct)\s+(?:\w+\s+)?(\w+)\s*(?:final\s*)?[:{]",
+            ),
+            (
+                Type,
+                r"(?m)^(?:typedef\s+)?(?:union|enum(?:\s+class)?)\s+(\w+)\s*[:{]",
+            ),
+            (Module, r"(?m)^namespace\s+([\w:]+)"),
+        ],
+        "ex" | "exs" => &[
+            (Module, r"(?m)^\s*defmodule\s+([\w.]+)"),
+            (Trait, r"(?m)^\s*defprotocol\s+([\w.]+)"),
+            (Function, r"(?m)^\s*def\s+([a-z_]\w*[?!]?)"),
+        ],
+        "hs" => &[
+            (Module, r"(?m)^module\s+([\w.]+)"),
+            (
+                Type,
+                r"(?m)^(?:data|newtype|type)\s+(?:family\s+)?([A-Z]\w*)",
+            ),
+            (
+                Trait,
+                r"(?m)^class\s+(?:(?:\([^)]*\)|\w+\s+\w+)\s*=>\s*)?([A-Z]\w*)",
+            ),
+            (Function, r"(?m)^([a-z]\w*'*)\s*::"),
+        ],
+        "zig" => &[
+            (
+                Type,
+                r"(?m)^pub\s+const\s+(\w+)\s*=\s*(?:extern\s+|packed\s+)?(?:struct|enum|union|opaque)",
+            ),
+            (Function, r"(?m)^pub\s+(?:inline\s+|export\s+)?fn\s+(\w+)"),
+        ],
+        "lua" => &[(Function, r"(?m)^function\s+([\w.:]+)")],
+        _ => &[],
+    }
+}
+
+/// Compiled declaration patterns of one language
+type Patterns = Vec<(SymbolKind, Regex)>;
+
+/// Compiled patterns per extension, built on first use
+fn compiled(ext: &str) -> &'static [(SymbolKind, Regex)] {
+    static CACHE: OnceLock<Vec<(&'static str, Patterns)>> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| {
+        SOURCE_EXTENSIONS
+            .iter()
+            .map(|ext| {
+                let regexes = patterns(ext)
+                    .iter()
+                    .map(|(kind, pattern)| {
+                        (*kind, Regex::new(pattern).expect("valid symbol pattern"))
+                    })
+                    .collect();
+                (*ext, regexes)
+            })
+            .collect()
+    });
+    cache
+        .iter()
+        .find(|(e, _)| *e == ext)
+        .map(|(_, r)| r.as_slice())
+        .unwrap_or(&[])
+}
+
+/// Symbols declared in a source file, in order of appearance per kind
+pub fn extract(content: &str, ext: &str) -> Vec<(String, SymbolKind)> {
+    let mut symbols: Vec<(String, SymbolKind)> = Vec::new();
+
+    for (kind, re) in compiled(ext) {
+        for cap in re.captures_iter(content) {
+            let name = cap[1].to_string();
+            let keep = match kind {
+                // Types start with an uppercase letter by convention (C aside)
+                Type | Trait => {
+                    matches!(ext, "c" | "h")
+                        || name.chars().next().is_some_and(|c| c.is_uppercase())
+                }
+                Function => !name.starts_with('_') && !C_KEYWORDS.contains(&name.as_str()),
+                Module => true,
+            };
+            if keep && !symbols.iter().any(|(n, k)| *n == name && k == kind) {
+                symbols.push((name, *kind));
+            }
+        }
+    }
+
+    symbols
+}
+
+/// Symbol whose name matches the most query terms (the shortest name on
+/// ties), shown next to a result to tell where the query matched
+pub fn best_match<'a>(query: &str, symbols: &'a [Symbol]) -> Option<&'a Symbol> {
+    symbols
+        .iter()
+        .map(|symbol| (lexical::matched_terms(query, &symbol.name), symbol))
+        .filter(|(matched, _)| *matched > 0)
+        .min_by_key(|(matched, symbol)| (std::cmp::Reverse(*matched), symbol.name.len()))
+        .map(|(_, symbol)| symbol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Names extracted from a snippet, by kind
+    fn names(content: &str, ext: &str, kind: SymbolKind) -> Vec<String> {
+        extract(content, ext)
+            .into_iter()
+            .filter(|(_, k)| *k == kind)
+            .map(|(n, _)| n)
+            .collect()
+    }
+
+    #[test]
+    fn rust_public_items_only() {
+        let code = "pub struct LruCache;\nstruct Private;\npub trait Evict {}\npub mod shard;\n\
+                    pub async fn open_cache() {}\nfn helper() {}\nimpl LruCache {\n    pub fn get(&self) {}\n}\n";
+        assert_eq!(names(code, "rs", Type), ["LruCache"]);
+        assert_eq!(names(code, "rs", Trait), ["Evict"]);
+        assert_eq!(names(code, "rs", Module), ["shard"]);
+        assert_eq!(names(code, "rs", Function), ["open_cache"]);
+    }
+
+    #[test]
+    fn kotlin_data_classes_and_objects() {
+        let code = "data class Invoice(val id: Long)\nobject Registry\ninterface Repository\nsuspend fun loadAll() {}\n";
+        assert_eq!(names(code, "kt", Type), ["Invoice", "Registry"]);
+        assert_eq!(names(code, "kt", Trait), ["Repository"]);
+        assert_eq!(names(code, "kt", Function), ["loadAll"]);
+    }
+
+    #[test]
+    fn ruby_classes_modules_and_methods() {
+        let code = "module Billing\n  class InvoiceMailer < Base\n    def self.deliver_later\n    end\n\n    \
+                    def paid?\n    end\n\n    def _internal\n    end\n  end\nend\n";
+        assert_eq!(names(code, "rb", Module), ["Billing"]);
+        assert_eq!(names(code, "rb", Type), ["InvoiceMailer"]);
+        assert_eq!(names(code, "rb", Function), ["deliver_later", "paid?"]);
+    }
+
+    #[test]
+    fn go_exported_declarations() {
+        let code = "type Server struct {}\ntype handler struct {}\ntype Store interface {}\nfunc NewServer() *Server {}\nfunc (s *Server) Run() {}\n";
+        assert_eq!(names(code, "go", Type), ["Server"]);
+        assert_eq!(names(code, "go", Trait), ["Store"]);
+        assert_eq!(names(code, "go", Function), ["NewServer"]);
+    }
+
+    #[test]
+    fn python_top_level_definitions() {
+        let code = "class Pipeline:\n    def run(self):\n        pass\n\nasync def fetch_all():\n    pass\n\ndef _private():\n    pass\n";
+        assert_eq!(names(code, "py", Type), ["Pipeline"]);
+        assert_eq!(names(code, "py", Function), ["fetch_all"]);
+    }
+
+    #[test]
+    fn typescript_exports() {
+        let code = "export class ApiClient {}\nexport type Options = {};\nexport interface Transport {}\nexport default async function connect() {}\n";
+        assert_eq!(names(code, "ts", Type), ["ApiClient", "Options"]);
+        assert_eq!(names(code, "ts", Trait), ["Transport"]);
+        assert_eq!(names(code, "ts", Function), ["connect"]);
+    }
+
+    #[test]
+    fn c_functions_skip_keywords() {
+        let code = "typedef struct ring_buffer {\n  int head;\n} ring_buffer;\n\nint ring_push(struct ring_buffer *rb, int v) {\n  if (x) {}\n}\n";
+        assert_eq!(names(code, "c", Type), ["ring_buffer"]);
+        assert_eq!(names(code, "c", Function), ["ring_push"]);
+    }
+
+    #[test]
+    fn elixir_haskell_swift_php_zig_lua() {
+        assert_eq!(
+            names(
+                "defmodule Chat.Room do\n  def join(user) do\n  end\nend\n",
+                "ex",
+                Module
+            ),
+            ["Chat.Room"]
+        );
+        assert_eq!(
+            names(
+                "defmodule Chat.Room do\n  def join(user) do\n  end\nend\n",
+                "ex",
+                Function
+            ),
+            ["join"]
+        );
+        assert_eq!(
+            names(
+                "module Data.Queue where\nclass Monad m => MonadQueue m where\n",
+                "hs",
+                Trait
+            ),
+            ["MonadQueue"]
+        );
+        assert_eq!(
+            names(
+                "public final class Player {}\nprotocol Playable {}\n",
+                "swift",
+                Type
+            ),
+            ["Player"]
+        );
+        assert_eq!(
+            names(
+                "namespace App\\Http;\nfinal class Kernel {}\n",
+                "php",
+                Module
+            ),
+            ["App\\Http"]
+        );
+        assert_eq!(
+            names(
+                "pub const Allocator = struct {};\npub fn init() void {}\n",
+                "zig",
+                Type
+            ),
+            ["Allocator"]
+        );
+        assert_eq!(
+            names("function M.setup(opts)\nend\n", "lua", Function),
+            ["M.setup"]
+        );
+    }
+
+    #[test]
+    fn best_match_prefers_more_terms_then_shorter_names() {
+        let symbol = |name: &str| Symbol {
+            name: name.to_string(),
+            kind: Type,
+            file: PathBuf::from("src/lib.rs"),
+        };
+        let symbols = [
+            symbol("CacheEntry"),
+            symbol("LruCache"),
+            symbol("LruCacheBuilder"),
+            symbol("Shard"),
+        ];
+        assert_eq!(best_match("lru cache", &symbols).unwrap().name, "LruCache");
+        assert_eq!(best_match("cache", &symbols).unwrap().name, "LruCache");
+        assert!(best_match("kafka", &symbols).is_none());
+    }
+}
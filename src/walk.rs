@@ -0,0 +1,119 @@
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+
+/// Per-directory ignore file read only by goto, same syntax as .gitignore
+/// (for folders that are tracked in git but are noise for search, e.g. SDK copies)
+pub const IGNORE_FILE_NAME: &str = ".gotoignore";
+
+/// Ignore files that change what metadata extraction sees
+pub const IGNORE_FILES: &[&str] = &[".gitignore", ".ignore", IGNORE_FILE_NAME];
+
+/// Build output and vendored dependencies that metadata extraction skips even
+/// when a project doesn't ignore them (gitignore syntax)
+pub const DEFAULT_IGNORES: &[&str] = &[
+    "node_modules/",
+    "target/",
+    "build/",
+    "dist/",
+    "vendor/",
+    "generated/",
+];
+
+/// Directory walker that skips hidden entries and everything ignored by
+/// .gitignore, .ignore, .gotoignore, .git/info/exclude, the global git
+/// excludes file and goto's global ignore file (`ignore` next to config.toml)
+pub fn walker(root: &Path, max_depth: usize) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .max_depth(Some(max_depth))
+        .follow_links(false)
+        // .gitignore also applies to folders that aren't repositories (copies, archives)
+        .require_git(false)
+        .add_custom_ignore_filename(IGNORE_FILE_NAME);
+
+    if let Ok(global) = Config::config_dir().map(|dir| dir.join("ignore")) {
+        if global.is_file() {
+            // Unreadable or malformed patterns are skipped, like git does
+            let _ = builder.add_ignore(global);
+        }
+    }
+
+    builder
+}
+
+/// Walker for metadata extraction: `walker` that also skips DEFAULT_IGNORES,
+/// the given patterns (gitignore syntax, case-insensitive) and the given
+/// directories (workspace members, indexed as projects of their own)
+pub fn extraction_walker(
+    root: &Path,
+    max_depth: usize,
+    ignores: &[&str],
+    skipped_dirs: &[PathBuf],
+) -> WalkBuilder {
+    let mut builder = walker(root, max_depth);
+    if !skipped_dirs.is_empty() {
+        let skipped_dirs = skipped_dirs.to_vec();
+        builder.filter_entry(move |entry| !skipped_dirs.iter().any(|dir| entry.path() == dir));
+    }
+
+    let mut overrides = OverrideBuilder::new(root);
+    overrides
+        .case_insensitive(true)
+        .expect("case-insensitive overrides");
+    for pattern in DEFAULT_IGNORES.iter().chain(ignores) {
+        // In overrides, `!` marks a pattern to ignore
+        overrides
+            .add(&format!("!{}", pattern))
+            .expect("valid ignore pattern");
+    }
+    builder.overrides(overrides.build().expect("valid ignore patterns"));
+
+    builder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn extraction_skips_build_output_given_patterns_and_members() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for file in [
+            "src/lib.rs",
+            "Target/debug/out.rs",
+            "web/node_modules/dep/index.js",
+            "tests/it.rs",
+            "src/api_test.go",
+            "crates/billing/src/lib.rs",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        let mut files: Vec<String> = extraction_walker(
+            root,
+            8,
+            &["tests/", "*_test.*"],
+            &[root.join("crates/billing")],
+        )
+        .build()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .map(|e| {
+            e.path()
+                .strip_prefix(root)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect();
+        files.sort();
+        assert_eq!(files, ["src/lib.rs"]);
+    }
+}
@@ -0,0 +1,530 @@
+use regex_lite::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use crate::db::Project;
+use crate::fuzzy;
+
+/// Directories never descended into when expanding `**` member patterns
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "build", "dist", "vendor"];
+
+/// Maximum depth explored by a `**` segment
+const RECURSIVE_GLOB_DEPTH: usize = 4;
+
+/// Minimum fuzzy score for `repo/mem` to pick a member by abbreviation
+const MEMBER_FUZZY_MIN_SCORE: f32 = 50.0;
+
+/// Find the members of a workspace rooted at `root`
+///
+/// Understands Cargo workspaces, npm/yarn/pnpm workspaces, Go workspaces
+/// (go.work) and Gradle multi-project builds. Returns an empty list for
+/// a regular project.
+pub fn members(root: &Path) -> Vec<PathBuf> {
+    let mut members = Vec::new();
+    members.extend(cargo_members(root));
+    members.extend(npm_members(root));
+    members.extend(pnpm_members(root));
+    members.extend(go_work_members(root));
+    members.extend(gradle_members(root));
+
+    members.retain(|m| m != root && m.starts_with(root));
+    members.sort();
+    members.dedup();
+    members
+}
+
+/// `[workspace] members = ["crates/*"]` in Cargo.toml
+fn cargo_members(root: &Path) -> Vec<PathBuf> {
+    let Ok(content) = fs::read_to_string(root.join("Cargo.toml")) else {
+        return Vec::new();
+    };
+    let Ok(manifest) = content.parse::<toml::Value>() else {
+        return Vec::new();
+    };
+    let Some(workspace) = manifest.get("workspace") else {
+        return Vec::new();
+    };
+
+    let patterns = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    expand_members(
+        root,
+        &patterns("members"),
+        &patterns("exclude"),
+        "Cargo.toml",
+    )
+}
+
+/// `"workspaces": ["packages/*"]` or `"workspaces": {"packages": [...]}` in package.json
+fn npm_members(root: &Path) -> Vec<PathBuf> {
+    let Ok(content) = fs::read_to_string(root.join("package.json")) else {
+        return Vec::new();
+    };
+    let Ok(package) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+
+    // Object form: the array is under "packages"
+    let workspaces = &package["workspaces"];
+    let list = workspaces.get("packages").unwrap_or(workspaces);
+    let list: Vec<String> = list
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let (excludes, includes): (Vec<String>, Vec<String>) =
+        list.into_iter().partition(|p| p.starts_with('!'));
+    let excludes: Vec<String> = excludes.iter().map(|p| p[1..].to_string()).collect();
+
+    expand_members(root, &includes, &excludes, "package.json")
+}
+
+/// `packages:` list in pnpm-workspace.yaml
+fn pnpm_members(root: &Path) -> Vec<PathBuf> {
+    let Ok(content) = fs::read_to_string(root.join("pnpm-workspace.yaml")) else {
+        return Vec::new();
+    };
+
+    let mut includes = Vec::new();
+    let mut excludes = Vec::new();
+    let mut in_packages = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if !line.starts_with(' ') && !line.starts_with('-') && !trimmed.is_empty() {
+            in_packages = trimmed.starts_with("packages:");
+            continue;
+        }
+        if !in_packages {
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix('-') {
+            let pattern = item.trim().trim_matches(|c| c == '\'' || c == '"');
+            match pattern.strip_prefix('!') {
+                Some(excluded) => excludes.push(excluded.to_string()),
+                None => includes.push(pattern.to_string()),
+            }
+        }
+    }
+
+    expand_members(root, &includes, &excludes, "package.json")
+}
+
+/// `use ./svc` or `use ( ./a ./b )` in go.work
+fn go_work_members(root: &Path) -> Vec<PathBuf> {
+    let Ok(content) = fs::read_to_string(root.join("go.work")) else {
+        return Vec::new();
+    };
+
+    let mut dirs = Vec::new();
+    let mut in_block = false;
+
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        if in_block {
+            if line.starts_with(')') {
+                in_block = false;
+            } else if !line.is_empty() {
+                dirs.push(line.to_string());
+            }
+        } else if let Some(rest) = line.strip_prefix("use") {
+            let rest = rest.trim();
+            if rest.starts_with('(') {
+                in_block = true;
+            } else if !rest.is_empty() {
+                dirs.push(rest.to_string());
+            }
+        }
+    }
+
+    expand_members(root, &dirs, &[], "go.mod")
+}
+
+/// `include(":api", ":libs:core")` / `include 'api'` in settings.gradle(.kts)
+fn gradle_members(root: &Path) -> Vec<PathBuf> {
+    let content = ["settings.gradle.kts", "settings.gradle"]
+        .iter()
+        .find_map(|name| fs::read_to_string(root.join(name)).ok());
+    let Some(content) = content else {
+        return Vec::new();
+    };
+
+    static INCLUDE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"^\s*include\s*\(?(.*)"#).unwrap());
+    static QUOTED: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"["']([^"']+)["']"#).unwrap());
+
+    let mut dirs = Vec::new();
+    for line in content.lines() {
+        let Some(caps) = INCLUDE.captures(line) else {
+            continue;
+        };
+        for name in QUOTED.captures_iter(&caps[1]) {
+            let dir = name[1].trim_start_matches(':').replace(':', "/");
+            if !dir.is_empty() {
+                dirs.push(dir);
+            }
+        }
+    }
+
+    expand_members(root, &dirs, &[], "")
+}
+
+/// Expand member patterns into existing directories, minus the excluded ones.
+/// Globbed directories must contain `manifest` (when given) to count.
+fn expand_members(
+    root: &Path,
+    includes: &[String],
+    excludes: &[String],
+    manifest: &str,
+) -> Vec<PathBuf> {
+    let excluded: Vec<PathBuf> = excludes
+        .iter()
+        .flat_map(|p| expand_pattern(root, p))
+        .collect();
+
+    includes
+        .iter()
+        .flat_map(|pattern| {
+            let is_glob = pattern.contains('*') || pattern.contains('?');
+            expand_pattern(root, pattern)
+                .into_iter()
+                .filter(move |dir| !is_glob || manifest.is_empty() || dir.join(manifest).is_file())
+        })
+        .filter(|dir| !excluded.contains(dir))
+        .collect()
+}
+
+/// Expand a relative directory pattern (`*`, `?` and `**` segments) under `root`
+fn expand_pattern(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut current = vec![root.to_path_buf()];
+
+    for segment in pattern.split('/') {
+        if segment.is_empty() || segment == "." {
+            continue;
+        }
+
+        current = match segment {
+            ".." => current
+                .iter()
+                .filter_map(|dir| dir.parent().map(Path::to_path_buf))
+                .collect(),
+            "**" => current
+                .iter()
+                .flat_map(|dir| descendants(dir, RECURSIVE_GLOB_DEPTH))
+                .collect(),
+            _ if segment.contains('*') || segment.contains('?') => current
+                .iter()
+                .flat_map(|dir| subdirectories(dir))
+                .filter(|dir| {
+                    dir.file_name()
+                        .map(|n| wildcard_match(segment, &n.to_string_lossy()))
+                        .unwrap_or(false)
+                })
+                .collect(),
+            _ => current
+                .iter()
+                .map(|dir| dir.join(segment))
+                .filter(|dir| dir.is_dir())
+                .collect(),
+        };
+    }
+
+    current
+}
+
+/// Visible subdirectories of a directory (skipping dependency/build folders)
+fn subdirectories(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str())
+        })
+        .map(|e| e.path())
+        .collect()
+}
+
+/// A directory and all its subdirectories up to `depth` levels down
+fn descendants(dir: &Path, depth: usize) -> Vec<PathBuf> {
+    let mut all = vec![dir.to_path_buf()];
+    if depth > 0 {
+        for child in subdirectories(dir) {
+            all.extend(descendants(&child, depth - 1));
+        }
+    }
+    all
+}
+
+/// Match a file name against a pattern with `*` and `?` wildcards
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Resolve a `repo/member` query to a workspace member
+///
+/// `repo` must name the parent project. `member` is compared to the member
+/// name, or to its path relative to the parent (`platform/crates/billing`).
+pub fn resolve_member<'a>(query: &str, projects: &'a [Project]) -> Option<&'a Project> {
+    let (repo, member) = query.split_once('/')?;
+    let repo = fuzzy::fold(repo);
+    let member = fuzzy::fold(member.trim_end_matches('/'));
+    if repo.is_empty() || member.is_empty() {
+        return None;
+    }
+
+    let candidates: Vec<(&Project, &Path)> = projects
+        .iter()
+        .filter_map(|p| p.parent.as_deref().map(|parent| (p, parent)))
+        .filter(|(_, parent)| {
+            parent
+                .file_name()
+                .map(|n| fuzzy::fold(&n.to_string_lossy()) == repo)
+                .unwrap_or(false)
+        })
+        .collect();
+
+    let exact = candidates.iter().find(|(p, parent)| {
+        let relative = p
+            .path
+            .strip_prefix(parent)
+            .map(|r| r.to_string_lossy().to_string())
+            .unwrap_or_default();
+        fuzzy::fold(&p.name) == member || fuzzy::fold(&relative) == member
+    });
+    if let Some((project, _)) = exact {
+        return Some(project);
+    }
+
+    // Otherwise the best fuzzy match among that workspace's members
+    candidates
+        .iter()
+        .map(|(p, _)| (*p, fuzzy::score(&member, &p.name)))
+        .filter(|(_, score)| *score >= MEMBER_FUZZY_MIN_SCORE)
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(p, _)| p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create files (with their directories) under `root`
+    fn write(root: &Path, files: &[(&str, &str)]) {
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+    }
+
+    fn relative(root: &Path, members: Vec<PathBuf>) -> Vec<String> {
+        members
+            .iter()
+            .map(|m| m.strip_prefix(root).unwrap().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn cargo_members_expand_globs_minus_excludes() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            &[
+                ("Cargo.toml", "[workspace]\nmembers = [\"crates/*\", \"tools/cli\"]\nexclude = [\"crates/old\"]\n"),
+                ("crates/api/Cargo.toml", ""),
+                ("crates/core/Cargo.toml", ""),
+                ("crates/old/Cargo.toml", ""),
+                ("crates/docs/README.md", ""),
+                ("tools/cli/Cargo.toml", ""),
+            ],
+        );
+        assert_eq!(
+            relative(dir.path(), members(dir.path())),
+            ["crates/api", "crates/core", "tools/cli"]
+        );
+    }
+
+    #[test]
+    fn npm_members_read_both_workspace_forms() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            &[
+                (
+                    "package.json",
+                    r#"{"workspaces": ["packages/*", "!packages/legacy"]}"#,
+                ),
+                ("packages/ui/package.json", "{}"),
+                ("packages/legacy/package.json", "{}"),
+            ],
+        );
+        assert_eq!(relative(dir.path(), members(dir.path())), ["packages/ui"]);
+
+        write(
+            dir.path(),
+            &[(
+                "package.json",
+                r#"{"workspaces": {"packages": ["packages/legacy"]}}"#,
+            )],
+        );
+        assert_eq!(
+            relative(dir.path(), members(dir.path())),
+            ["packages/legacy"]
+        );
+    }
+
+    #[test]
+    fn pnpm_members_read_the_packages_list() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            &[
+                (
+                    "pnpm-workspace.yaml",
+                    "packages:\n  - 'apps/*'\n  - \"!apps/sandbox\"\ncatalog:\n  - 'libs/*'\n",
+                ),
+                ("apps/web/package.json", "{}"),
+                ("apps/sandbox/package.json", "{}"),
+                ("libs/util/package.json", "{}"),
+            ],
+        );
+        assert_eq!(relative(dir.path(), members(dir.path())), ["apps/web"]);
+    }
+
+    #[test]
+    fn go_work_members_read_single_and_block_uses() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            &[
+                ("go.work", "go 1.22\n\nuse ./cmd\nuse (\n\t./svc/auth // auth service\n\t./svc/missing\n)\n"),
+                ("cmd/go.mod", ""),
+                ("svc/auth/go.mod", ""),
+            ],
+        );
+        assert_eq!(
+            relative(dir.path(), members(dir.path())),
+            ["cmd", "svc/auth"]
+        );
+    }
+
+    #[test]
+    fn gradle_members_map_project_paths_to_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            &[
+                ("settings.gradle.kts", "rootProject.name = \"shop\"\ninclude(\":api\", \":libs:core\")\ninclude 'web'\n"),
+                ("api/build.gradle.kts", ""),
+                ("libs/core/build.gradle.kts", ""),
+                ("web/build.gradle", ""),
+            ],
+        );
+        assert_eq!(
+            relative(dir.path(), members(dir.path())),
+            ["api", "libs/core", "web"]
+        );
+    }
+
+    #[test]
+    fn regular_project_has_no_members() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            &[
+                ("Cargo.toml", "[package]\nname = \"solo\"\n"),
+                ("package.json", "{}"),
+            ],
+        );
+        assert!(members(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(wildcard_match("*", "anything"));
+        assert!(wildcard_match("svc-*", "svc-auth"));
+        assert!(wildcard_match("a?c*d", "abcxxd"));
+        assert!(!wildcard_match("svc-*", "api"));
+        assert!(!wildcard_match("a?c", "ac"));
+    }
+
+    fn member(path: &str, parent: &str) -> Project {
+        Project {
+            parent: Some(PathBuf::from(parent)),
+            ..Project::for_test(path)
+        }
+    }
+
+    #[test]
+    fn resolve_member_by_name_path_or_abbreviation() {
+        let projects = [
+            member("/code/shop/crates/billing", "/code/shop"),
+            member("/code/shop/crates/inventory", "/code/shop"),
+            member("/code/other/billing", "/code/other"),
+        ];
+        let resolve =
+            |q| resolve_member(q, &projects).map(|p| p.path.to_string_lossy().to_string());
+
+        assert_eq!(
+            resolve("shop/billing").as_deref(),
+            Some("/code/shop/crates/billing")
+        );
+        assert_eq!(
+            resolve("Shop/crates/inventory/").as_deref(),
+            Some("/code/shop/crates/inventory")
+        );
+        assert_eq!(
+            resolve("shop/inv").as_deref(),
+            Some("/code/shop/crates/inventory")
+        );
+        assert_eq!(
+            resolve("other/billing").as_deref(),
+            Some("/code/other/billing")
+        );
+        assert_eq!(resolve("shop/zzz"), None);
+        assert_eq!(resolve("billing"), None);
+    }
+}